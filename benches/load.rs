@@ -0,0 +1,57 @@
+//! Loading a generated directory of task files, the way norgdo does on startup.
+//! Parsing is spread over all cores with `std::thread::scope` (see
+//! `TaskManager::load_tasks`), so this also catches regressions in that split.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use norgdo::task_manager::TaskManager;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TASK_COUNT: usize = 2000;
+
+/// Writes `TASK_COUNT` tasks with metadata, a description and nested todos, a tenth
+/// of them in project subdirectories
+fn generate_corpus(dir: &Path) {
+    for i in 0..TASK_COUNT {
+        let dir = match i % 10 {
+            0 => dir.join(format!("project_{}", i % 7)),
+            _ => dir.to_path_buf(),
+        };
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut content = format!(
+            "@document.meta\nid: task-{i}\ncreated: 2025-01-10T09:30:00Z\nupdated: 2025-01-12T18:05:00Z\ntags: bench work\n@end\n\n* Task number {i}\n\nA short description of task {i} with a {{https://example.com}}[link].\n\n"
+        );
+        for todo in 0..8 {
+            let state = ["x", " ", "-", "!"][todo % 4];
+            let marker = if todo % 3 == 2 { "--" } else { "-" };
+            content.push_str(&format!("{marker} ({state}) Step {todo} of task {i}\n"));
+        }
+        fs::write(dir.join(format!("task_{i}.norg")), content).unwrap();
+    }
+}
+
+fn load(c: &mut Criterion) {
+    let dir: PathBuf = std::env::temp_dir().join(format!("norgdo-bench-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    generate_corpus(&dir);
+
+    let mut group = c.benchmark_group("load");
+    group.sample_size(10);
+    group.bench_function("full", |b| {
+        b.iter(|| TaskManager::new(Some(dir.clone())).unwrap())
+    });
+    group.bench_function("summaries", |b| {
+        b.iter(|| {
+            let mut manager = TaskManager::without_tasks(Some(dir.clone())).unwrap();
+            manager.load_summaries().unwrap();
+            manager
+        })
+    });
+    group.finish();
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+criterion_group!(benches, load);
+criterion_main!(benches);
@@ -1,209 +1,1151 @@
-use crate::task::{KanbanCategory, TodoState};
-use crate::task_manager::TaskManager;
+use crate::config::{Action, Config};
+use crate::help;
+use crate::input::TextInput;
+use crate::session::{SessionState, UiState};
+use crate::watcher::TaskWatcher;
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use norgdo::task::{KanbanCategory, Task, TodoItem, TodoState, parse_due_date};
+use norgdo::task_manager::{AgendaWindow, TaskFilter, TaskManager};
+use ratatui::layout::{Position, Rect};
 use ratatui::widgets::{ListState, ScrollbarState};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// How long a `g` waits for a second one to make a `gg` jump
+const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How often events are polled while tasks load in the background
+const LOADING_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long an info notification stays up, errors stay until dismissed
+const INFO_NOTIFICATION_DURATION: Duration = Duration::from_secs(4);
+
+/// How long a success notification stays up, just long enough to be noticed
+const SUCCESS_NOTIFICATION_DURATION: Duration = Duration::from_secs(2);
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppMode {
     Dashboard,
     TaskDetail(String), // task_id
     CreateTaskWizard(WizardStep),
+    CreateTask, // Quick-add: a single title field, no wizard
+    TemplatePicker,
+    CreateFromTemplate(String), // Title prompt for a task made from this template
     Search,
+    Filter, // Editing the dashboard filter
     Help,
-    TodoStateSelect { task_id: String, todo_index: usize },
+    ParseErrors,                      // Files that failed to parse
+    ConfirmQuit,                      // Quitting with unsaved changes
+    ConfirmDiscardWizard(WizardStep), // Leaving the wizard with something entered, from this step
+    TodoStateSelect {
+        task_id: String,
+        todo_index: usize,
+    },
+    EditTodo {
+        task_id: String,
+        todo_index: usize,
+    }, // Editing a todo's text in place
+    EditDueDate(String), // Typing the due date of a task
+    AllTodos,            // Every todo across all tasks in one list
+    TaskPicker {
+        purpose: PickerPurpose,
+        query: TextInput,
+        selected: usize, // Index into the tasks matching the query
+    },
+}
+
+/// What choosing a task in the task picker does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickerPurpose {
+    Jump, // Open the task
+}
+
+/// What a key means for the `gg`/`G` motions of vim navigation
+enum VimMotion {
+    None,         // Not part of a motion, handle the key as usual
+    Pending,      // The first `g` of `gg`
+    Jump(Action), // Jump to the top or bottom of the list
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum WizardStep {
     Title,
     Description,
+    DueDate,
     Todos,
     Confirm,
 }
 
-#[derive(Debug, Clone)]
+impl WizardStep {
+    /// Every step, in the order the wizard goes through them
+    pub const ALL: [WizardStep; 5] = [
+        WizardStep::Title,
+        WizardStep::Description,
+        WizardStep::DueDate,
+        WizardStep::Todos,
+        WizardStep::Confirm,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WizardStep::Title => "Title",
+            WizardStep::Description => "Description",
+            WizardStep::DueDate => "Due Date",
+            WizardStep::Todos => "TODO Items",
+            WizardStep::Confirm => "Confirm",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct TaskWizardData {
-    pub title: String,
-    pub description: String,
-    pub todos: Vec<String>,
-    pub current_todo: String,
+    pub title: TextInput,
+    pub description: TextInput,
+    pub due_date: TextInput, // As typed, see `parse_due_date`
+    pub todos: Vec<TodoItem>,
+    pub current_todo: TextInput,
+    pub current_todo_indent: usize, // Nesting depth of the todo being typed, 0 for top-level
+    pub current_todo_state: TodoState, // Initial state of the todo being typed
     pub selected_todo_index: Option<usize>, // For editing/deleting todos
-    pub editing_todo_index: Option<usize>,  // For preserving order when editing
+    pub editing_todo_index: Option<usize>, // For preserving order when editing
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl TaskWizardData {
+    /// Whether nothing was entered yet, so the wizard can be left without losing anything
+    pub fn is_empty(&self) -> bool {
+        self.title.value().trim().is_empty()
+            && self.description.value().trim().is_empty()
+            && self.due_date.value().trim().is_empty()
+            && self.todos.is_empty()
+            && self.current_todo.value().trim().is_empty()
+    }
+
+    /// The due date typed, relative to today, or `None` when it was left empty
+    pub fn parsed_due_date(&self) -> Result<Option<chrono::NaiveDate>> {
+        let input = self.due_date.value().trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        parse_due_date(input, chrono::Local::now().date_naive()).map(Some)
+    }
+}
+
+/// A request to suspend the UI and open a task file in the user's editor
+#[derive(Debug, Clone)]
+pub struct EditorRequest {
+    pub editor: String,
+    pub file_path: PathBuf,
+    pub line_number: Option<usize>,
+}
+
+/// Where a list was last drawn and how tall each of its rows is, recorded while
+/// rendering so mouse clicks can be mapped back to list items
+#[derive(Debug, Clone, Default)]
+pub struct ListLayout {
+    pub area: Rect, // Inner area of the list, without its borders
+    pub item_heights: Vec<u16>,
+}
+
+impl ListLayout {
+    /// Index of the item drawn at the given screen cell, if any
+    pub fn item_at(&self, column: u16, row: u16, offset: usize) -> Option<usize> {
+        if !self.area.contains(Position::new(column, row)) {
+            return None;
+        }
+
+        let mut top = self.area.y;
+        for (index, height) in self.item_heights.iter().enumerate().skip(offset) {
+            if row < top.saturating_add(*height) {
+                return Some(index);
+            }
+            top = top.saturating_add(*height);
+        }
+        None
+    }
+
+    /// Number of whole items that fit in the area when scrolled to `offset`
+    pub fn visible_items(&self, offset: usize) -> usize {
+        let mut height = 0;
+        self.item_heights
+            .iter()
+            .skip(offset)
+            .take_while(|item_height| {
+                height += **item_height;
+                height <= self.area.height
+            })
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FocusedPane {
+    #[default]
     YetToBeDone,
     InProgress,
     Completed,
+    Custom(String), // A column from the config, by name
+}
+
+impl FocusedPane {
+    pub fn category(&self) -> KanbanCategory {
+        match self {
+            FocusedPane::YetToBeDone => KanbanCategory::YetToBeDone,
+            FocusedPane::InProgress => KanbanCategory::InProgress,
+            FocusedPane::Completed => KanbanCategory::Completed,
+            FocusedPane::Custom(name) => KanbanCategory::Custom(name.clone()),
+        }
+    }
+
+    pub fn from_category(category: &KanbanCategory) -> Self {
+        match category {
+            KanbanCategory::YetToBeDone => FocusedPane::YetToBeDone,
+            KanbanCategory::InProgress => FocusedPane::InProgress,
+            KanbanCategory::Completed => FocusedPane::Completed,
+            KanbanCategory::Custom(name) => FocusedPane::Custom(name.clone()),
+        }
+    }
+}
+
+/// How urgent a notification is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Success, // Confirms an action, goes away on its own
+    Info,    // Goes away on its own
+    Error,   // Stays until dismissed
+}
+
+impl Severity {
+    /// How long a notification stays up, `None` if it has to be dismissed
+    fn duration(self) -> Option<Duration> {
+        match self {
+            Severity::Success => Some(SUCCESS_NOTIFICATION_DURATION),
+            Severity::Info => Some(INFO_NOTIFICATION_DURATION),
+            Severity::Error => None,
+        }
+    }
+}
+
+/// A message shown in the notification stack
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub severity: Severity,
+    pub message: String,
+    pub created: Instant,
+}
+
+/// Tasks being loaded on another thread, swapped in by `finish_loading` once done
+struct PendingLoad {
+    result: Receiver<Result<TaskManager>>,
+    purpose: LoadPurpose,
+    started: Instant,
+}
+
+/// What to do with the tasks once they are loaded
+enum LoadPurpose {
+    Startup(SessionState), // Restore the view of the last run
+    Refresh(Selection),    // Follow the selected tasks to where they are now
+}
+
+/// The tasks selected on the dashboard, by id
+struct Selection {
+    column_task_ids: HashMap<KanbanCategory, String>,
+    project_task_id: Option<String>,
+}
+
+/// How the dashboard groups tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardMode {
+    #[default]
+    Status, // One column per kanban category
+    Project, // One swimlane per project subdirectory
 }
 
 pub struct App {
     pub task_manager: TaskManager,
+    pub config: Config,
     pub mode: AppMode,
     pub focused_pane: FocusedPane,
     pub list_states: HashMap<KanbanCategory, ListState>,
+    pub scrollbar_states: HashMap<KanbanCategory, ScrollbarState>, // For the column scrollbars
+    pub board_mode: BoardMode,
+    pub show_completed: bool, // Whether the dashboard shows the Completed column
+    pub project_list_state: ListState, // Rows of the project view, lane headers included
     pub should_quit: bool,
-    pub search_query: String,
-    pub error_message: Option<String>,
-    pub todo_list_state: ListState, // For navigating todos in task detail view
-    pub help_scroll_offset: u16,    // For scrolling help content
+    pub search_input: TextInput,
+    pub notifications: Vec<Notification>,     // Oldest first
+    pub todo_list_state: ListState,           // For navigating todos in task detail view
+    pub help_list_state: ListState,           // For scrolling help content, by its offset
+    pub help_section: usize,                  // Section last scrolled to, shown in the help title
+    pub help_page_height: u16, // Lines of help shown at once, recorded while rendering
+    pub help_max_scroll: usize, // Offset showing the last line of help, recorded while rendering
     pub help_scrollbar_state: ScrollbarState, // For help scrollbar widget
+    help_return_mode: AppMode, // The view the help popup was opened from
+    pub picker_return_mode: AppMode, // The view the task picker was opened from
+    pending_g: Option<Instant>, // When a `g` was pressed that may start a `gg`
     pub wizard_data: TaskWizardData, // For task creation wizard
+    pub new_task_title: TextInput, // Title being typed in the quick-add prompt
+    pub templates: Vec<String>, // Template names offered by the template picker
+    pub template_list_state: ListState, // For navigating the template picker
     pub todo_state_list_state: ListState, // For selecting TODO states
+    pub todo_text_input: TextInput, // Text of the todo being edited in place
+    pub due_date_input: TextInput, // Due date being typed, see `parse_due_date`
+    pub all_todos_list_state: ListState, // For navigating the list of all todos
+    pub all_todos_state_filter: Option<TodoState>, // Only list todos in this state
+    pub editor_request: Option<EditorRequest>, // Handled by the main loop, which owns the terminal
+    pub search_list_state: ListState, // For navigating search results
+    pub parse_error_list_state: ListState, // For navigating files that failed to parse
+    pub filter: TaskFilter,    // Restricts the tasks shown on the dashboard
+    pub filter_input: TextInput, // Query being typed in the filter bar
+    pub column_layouts: HashMap<KanbanCategory, ListLayout>, // For mapping clicks to cards
+    pub todo_list_layout: ListLayout, // For mapping clicks to todos in task detail view
+    pub description_scroll: u16, // For scrolling long descriptions in task detail view
+    pub description_area: Rect, // For mapping the scroll wheel to the description
+    complete_all_undo: Option<(String, Vec<TodoState>)>, // Todo states before completing a task at once
+    task_watcher: Option<TaskWatcher>, // Reloads tasks when files change outside norgdo
+    pending_load: Option<PendingLoad>, // Tasks loading in the background
+    pending_bodies: Option<Receiver<Vec<Result<Task>>>>, // Full tasks parsing in the background
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
-        let task_manager = TaskManager::new()?;
+    /// Starts loading the tasks of `data_dir`, restoring the view saved in `session` once
+    /// they're in
+    pub fn new(data_dir: Option<PathBuf>, config: Config, session: SessionState) -> Result<Self> {
+        let mut task_manager = TaskManager::without_tasks(data_dir)?;
+        task_manager.set_auto_save(config.auto_save);
+        task_manager.set_completion_rules(config.completion_rules());
+        task_manager.set_columns(config.columns.clone());
+        // Auto-reload is a convenience, so keep going without it if the watcher fails
+        // notify may deliver the events of a save after the next check, so norgdo's own
+        // writes are ignored until the tick after that
+        let task_watcher = TaskWatcher::new(task_manager.data_dir(), config.tick_rate() * 2).ok();
         let mut list_states = HashMap::new();
+        // Problems in the config are not fatal, so surface them once the UI is up
+        let notifications = config
+            .warnings()
+            .map(|warning| Notification {
+                severity: Severity::Error,
+                message: warning.clone(),
+                created: Instant::now(),
+            })
+            .collect();
 
         list_states.insert(KanbanCategory::YetToBeDone, ListState::default());
         list_states.insert(KanbanCategory::InProgress, ListState::default());
         list_states.insert(KanbanCategory::Completed, ListState::default());
+        for column in &config.columns {
+            list_states.insert(
+                KanbanCategory::Custom(column.name.clone()),
+                ListState::default(),
+            );
+        }
 
-        Ok(Self {
+        let mut app = Self {
             task_manager,
+            config,
             mode: AppMode::Dashboard,
             focused_pane: FocusedPane::YetToBeDone,
             list_states,
+            scrollbar_states: HashMap::new(),
+            board_mode: BoardMode::default(),
+            show_completed: true,
+            project_list_state: ListState::default(),
             should_quit: false,
-            search_query: String::new(),
-            error_message: None,
+            search_input: TextInput::default(),
+            notifications,
             todo_list_state: ListState::default(),
-            help_scroll_offset: 0,
+            help_list_state: ListState::default(),
+            help_section: 0,
+            help_page_height: 0,
+            help_max_scroll: 0,
             help_scrollbar_state: ScrollbarState::default(),
-            wizard_data: TaskWizardData {
-                title: String::new(),
-                description: String::new(),
-                todos: Vec::new(),
-                current_todo: String::new(),
-                selected_todo_index: None,
-                editing_todo_index: None,
-            },
+            help_return_mode: AppMode::Dashboard,
+            picker_return_mode: AppMode::Dashboard,
+            pending_g: None,
+            wizard_data: TaskWizardData::default(),
+            new_task_title: TextInput::default(),
+            templates: Vec::new(),
+            template_list_state: ListState::default(),
             todo_state_list_state: ListState::default(),
-        })
+            todo_text_input: TextInput::default(),
+            due_date_input: TextInput::default(),
+            all_todos_list_state: ListState::default(),
+            all_todos_state_filter: None,
+            editor_request: None,
+            search_list_state: ListState::default(),
+            parse_error_list_state: ListState::default(),
+            filter: TaskFilter::default(),
+            filter_input: TextInput::default(),
+            column_layouts: HashMap::new(),
+            todo_list_layout: ListLayout::default(),
+            description_scroll: 0,
+            description_area: Rect::default(),
+            complete_all_undo: None,
+            task_watcher,
+            pending_load: None,
+            pending_bodies: None,
+        };
+        // Big directories take a moment, so the UI starts right away with a spinner
+        app.start_loading(LoadPurpose::Startup(session));
+        Ok(app)
+    }
+
+    /// The most useful keys of the current view, for the bar at the bottom of the screen
+    pub fn mode_hint(&self) -> String {
+        use Action::*;
+
+        let hints: Vec<(Action, &str)> = match &self.mode {
+            AppMode::Dashboard | AppMode::Filter => {
+                let mut hints = vec![
+                    (Open, "open"),
+                    (NewTask, "new"),
+                    (Search, "search"),
+                    (Filter, "filter"),
+                ];
+                if self.filter.is_active() {
+                    hints.push((ClearFilter, "clear filter"));
+                }
+                hints.extend([(Help, "help"), (Quit, "quit")]);
+                hints
+            }
+            AppMode::TaskDetail(_) => {
+                let mut hints = vec![
+                    (SelectState, "state"),
+                    (EditTodo, "edit"),
+                    (EditDueDate, "due"),
+                    (OpenEditor, "editor"),
+                ];
+                if !self.config.auto_save {
+                    hints.push((Save, "save"));
+                }
+                hints.extend([(Help, "help"), (Back, "back")]);
+                hints
+            }
+            AppMode::AllTodos => vec![
+                (SelectState, "toggle state"),
+                (Open, "open task"),
+                (Filter, "filter by state"),
+                (Back, "back"),
+            ],
+            _ => Vec::new(),
+        };
+
+        hints
+            .into_iter()
+            .filter_map(|(action, label)| {
+                let key = self.config.keybindings.key_name(action)?;
+                Some(format!("{}: {}", key, label))
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Brings back the remembered view, focuses the remembered column and reselects
+    /// the remembered task, following it to whichever column it is in now
+    fn restore_session(&mut self, session: SessionState) {
+        self.board_mode = session.ui.board_mode;
+        self.show_completed = session.ui.show_completed;
+        self.filter = session.ui.filter;
+        // A tag no task has anymore would leave the board empty for no visible reason
+        if let Some(tag) = &self.filter.tag
+            && !self.task_manager.all_tags().contains(&tag.as_str())
+        {
+            self.filter.tag = None;
+        }
+        self.filter_input.set_value(self.filter.query.clone());
+
+        // A column removed from the config since then falls back to the first one
+        if self.board_panes().contains(&session.focused_pane) {
+            self.focused_pane = session.focused_pane;
+        }
+
+        let Some(task_id) = session.selected_task_id else {
+            return;
+        };
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
+        let found = tasks_by_category.iter().find_map(|(category, tasks)| {
+            let index = tasks.iter().position(|task| task.id == task_id)?;
+            Some((category.clone(), index))
+        });
+
+        if let Some((category, index)) = found {
+            // A hidden Completed column keeps its selection but never gets the focus
+            if category != KanbanCategory::Completed || self.show_completed {
+                self.focused_pane = FocusedPane::from_category(&category);
+            }
+            if let Some(state) = self.list_states.get_mut(&category) {
+                state.select(Some(index));
+            }
+        }
+    }
+
+    /// The dashboard view and selection to remember for the next run
+    pub fn session_state(&self) -> SessionState {
+        let category = self.focused_pane.category();
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
+        let selected_task_id = self
+            .list_states
+            .get(&category)
+            .and_then(ListState::selected)
+            .and_then(|index| tasks_by_category.get(&category)?.get(index))
+            .map(|task| task.id.clone());
+
+        SessionState {
+            focused_pane: self.focused_pane.clone(),
+            selected_task_id,
+            ui: UiState {
+                board_mode: self.board_mode,
+                show_completed: self.show_completed,
+                filter: self.filter.clone(),
+            },
+        }
     }
 
     pub fn handle_events(&mut self) -> Result<()> {
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Press {
-                return Ok(());
+        // Returning on timeout lets the main loop redraw on every tick, even without input
+        // Check on background loading more often, so the tasks show up as soon as they can
+        let timeout = if self.pending_load.is_some() || self.pending_bodies.is_some() {
+            self.config.tick_rate().min(LOADING_POLL_INTERVAL)
+        } else {
+            self.config.tick_rate()
+        };
+        if !event::poll(timeout)? {
+            return Ok(());
+        }
+
+        let event = event::read()?;
+        if self.pending_load.is_some() {
+            // Anything changed now would be lost when the loaded tasks are swapped in,
+            // so only quitting (and answering whether to save first) works until then
+            if let Event::Key(key) = event
+                && key.kind == KeyEventKind::Press
+            {
+                if self.mode == AppMode::ConfirmQuit {
+                    return self.handle_confirm_quit_input(key);
+                }
+                if self
+                    .config
+                    .keybindings
+                    .action(&[Action::Quit], key.code)
+                    .is_some()
+                {
+                    self.request_quit();
+                }
+            }
+            return Ok(());
+        }
+
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.handle_key_event(key),
+            // Like keys, the mouse and pastes wait until errors are dismissed
+            Event::Mouse(_) | Event::Paste(_) if self.has_errors() => Ok(()),
+            Event::Mouse(mouse) => self.handle_mouse_event(mouse),
+            Event::Paste(text) => {
+                self.handle_paste(&text);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Inserts pasted text into the input being typed in, if any. Line breaks are only
+    /// kept in the description, pasted lines become separate TODO items in the wizard,
+    /// and single-line fields get the lines joined with spaces.
+    fn handle_paste(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let single_line = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if let AppMode::TaskPicker {
+            query, selected, ..
+        } = &mut self.mode
+        {
+            query.insert_str(&single_line);
+            *selected = 0;
+            return;
+        }
+
+        match &self.mode {
+            AppMode::CreateTaskWizard(WizardStep::Title) => {
+                self.wizard_data.title.insert_str(&single_line);
+            }
+            AppMode::CreateTaskWizard(WizardStep::Description) => {
+                self.wizard_data.description.insert_str(&text);
+            }
+            AppMode::CreateTaskWizard(WizardStep::DueDate) => {
+                self.wizard_data.due_date.insert_str(&single_line);
+            }
+            AppMode::CreateTaskWizard(WizardStep::Todos) => {
+                // Every line but the last is added right away, the last one stays in
+                // the input like a todo being typed
+                let mut lines = text.split('\n').peekable();
+                while let Some(line) = lines.next() {
+                    self.wizard_data.current_todo.insert_str(line);
+                    if lines.peek().is_some() {
+                        self.commit_wizard_todo();
+                    }
+                }
+                self.wizard_data.selected_todo_index = None;
+            }
+            AppMode::CreateTask | AppMode::CreateFromTemplate(_) => {
+                self.new_task_title.insert_str(&single_line);
+            }
+            AppMode::EditTodo { .. } => self.todo_text_input.insert_str(&single_line),
+            AppMode::EditDueDate(_) => self.due_date_input.insert_str(&single_line),
+            AppMode::Search => {
+                self.search_input.insert_str(&single_line);
+                self.reset_search_selection();
+            }
+            AppMode::Filter => {
+                self.filter_input.insert_str(&single_line);
+                self.filter.query = self.filter_input.value().to_string();
+                self.clamp_list_selections();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
+        if self.has_errors() {
+            self.handle_error_popup_input(key);
+            return Ok(());
+        }
+
+        match &self.mode {
+            AppMode::Dashboard => self.handle_dashboard_input(key)?,
+            AppMode::TaskDetail(task_id) => {
+                let task_id = task_id.clone();
+                self.handle_task_detail_input(key, &task_id)?;
+            }
+            AppMode::CreateTaskWizard(step) => {
+                let step = step.clone();
+                self.handle_wizard_input(key, step)?;
+            }
+            AppMode::CreateTask => self.handle_create_task_input(key, None)?,
+            AppMode::TemplatePicker => self.handle_template_picker_input(key)?,
+            AppMode::CreateFromTemplate(template) => {
+                let template = template.clone();
+                self.handle_create_task_input(key, Some(&template))?;
+            }
+            AppMode::Search => self.handle_search_input(key)?,
+            AppMode::Filter => self.handle_filter_input(key)?,
+            AppMode::Help => self.handle_help_input(key)?,
+            AppMode::ParseErrors => self.handle_parse_errors_input(key)?,
+            AppMode::ConfirmQuit => self.handle_confirm_quit_input(key)?,
+            AppMode::ConfirmDiscardWizard(step) => {
+                let step = step.clone();
+                self.handle_confirm_discard_wizard_input(key, step)?;
+            }
+            AppMode::TodoStateSelect {
+                task_id,
+                todo_index,
+            } => {
+                let task_id = task_id.clone();
+                let todo_index = *todo_index;
+                self.handle_todo_state_select_input(key, &task_id, todo_index)?;
+            }
+            AppMode::EditTodo {
+                task_id,
+                todo_index,
+            } => {
+                let task_id = task_id.clone();
+                let todo_index = *todo_index;
+                self.handle_edit_todo_input(key, &task_id, todo_index)?;
+            }
+            AppMode::EditDueDate(task_id) => {
+                let task_id = task_id.clone();
+                self.handle_edit_due_date_input(key, &task_id)?;
+            }
+            AppMode::AllTodos => self.handle_all_todos_input(key)?,
+            AppMode::TaskPicker {
+                purpose,
+                query,
+                selected,
+            } => {
+                let (purpose, query, selected) = (*purpose, query.clone(), *selected);
+                self.handle_task_picker_input(key, purpose, query, selected)?;
             }
+        }
+        Ok(())
+    }
 
-            // Clear error message on any key press
-            self.error_message = None;
+    /// Left clicks select cards and todos, and the scroll wheel moves the selection or
+    /// scrolls the help popup. Clicking an already selected card opens it.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        let scroll = match mouse.kind {
+            MouseEventKind::ScrollUp => Some(-1),
+            MouseEventKind::ScrollDown => Some(1),
+            _ => None,
+        };
 
-            match &self.mode {
-                AppMode::Dashboard => self.handle_dashboard_input(key.code)?,
-                AppMode::TaskDetail(task_id) => {
-                    let task_id = task_id.clone();
-                    self.handle_task_detail_input(key.code, &task_id)?;
+        match self.mode.clone() {
+            AppMode::Dashboard => {
+                if let Some(direction) = scroll {
+                    // Scrolling past the end should stop there, never wrap around
+                    self.move_selection(direction, false);
+                    return Ok(());
                 }
-                AppMode::CreateTaskWizard(step) => {
-                    let step = step.clone();
-                    self.handle_wizard_input(key.code, step)?;
+                if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                    return Ok(());
                 }
-                AppMode::Search => self.handle_search_input(key.code)?,
-                AppMode::Help => self.handle_help_input(key.code)?,
-                AppMode::TodoStateSelect {
-                    task_id,
-                    todo_index,
-                } => {
-                    let task_id = task_id.clone();
-                    let todo_index = *todo_index;
-                    self.handle_todo_state_select_input(key.code, &task_id, todo_index)?;
+
+                let clicked = self.board_panes().into_iter().find_map(|pane| {
+                    let category = pane.category();
+                    let state = self.list_states.get(&category)?;
+                    let index = self.column_layouts.get(&category)?.item_at(
+                        mouse.column,
+                        mouse.row,
+                        state.offset(),
+                    )?;
+                    Some((category, pane, index))
+                });
+
+                if let Some((category, pane, index)) = clicked {
+                    let already_selected = self.focused_pane == pane
+                        && self.list_states[&category].selected() == Some(index);
+                    self.focused_pane = pane;
+                    if let Some(state) = self.list_states.get_mut(&category) {
+                        state.select(Some(index));
+                    }
+                    if already_selected {
+                        self.open_selected_task()?;
+                    }
                 }
             }
+            AppMode::TaskDetail(task_id) => {
+                if let Some(direction) = scroll {
+                    if self
+                        .description_area
+                        .contains(Position::new(mouse.column, mouse.row))
+                    {
+                        self.scroll_description(direction as i16);
+                    } else {
+                        self.move_todo_selection(&task_id, direction, false);
+                    }
+                    return Ok(());
+                }
+                if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                    && let Some(index) = self.todo_list_layout.item_at(
+                        mouse.column,
+                        mouse.row,
+                        self.todo_list_state.offset(),
+                    )
+                {
+                    self.todo_list_state.select(Some(index));
+                }
+            }
+            AppMode::Help => {
+                if let Some(direction) = scroll {
+                    self.scroll_help(direction as i16);
+                }
+            }
+            _ => {}
         }
         Ok(())
     }
 
-    fn handle_dashboard_input(&mut self, key_code: KeyCode) -> Result<()> {
-        match key_code {
-            KeyCode::Char('q') => self.should_quit = true,
-            KeyCode::Char('n') => {
+    fn handle_dashboard_input(&mut self, key: KeyEvent) -> Result<()> {
+        use Action::*;
+
+        let action = match self.vim_motion(key.code) {
+            VimMotion::Pending => return Ok(()),
+            VimMotion::Jump(action) => Some(action),
+            VimMotion::None => self.config.keybindings.action(
+                &[
+                    Quit,
+                    NewTask,
+                    QuickAdd,
+                    Search,
+                    JumpToTask,
+                    Left,
+                    Right,
+                    Up,
+                    Down,
+                    Open,
+                    Refresh,
+                    CycleTagFilter,
+                    Filter,
+                    ClearFilter,
+                    ShowParseErrors,
+                    Archive,
+                    ArchiveCompleted,
+                    ToggleBoardMode,
+                    Duplicate,
+                    NewFromTemplate,
+                    ToggleAgenda,
+                    ToggleAgendaOverdue,
+                    AllTodos,
+                    FocusYetToBeDone,
+                    FocusInProgress,
+                    FocusCompleted,
+                    FocusExtraColumn,
+                    ToggleCompleted,
+                    Top,
+                    Bottom,
+                    Help,
+                ],
+                key.code,
+            ),
+        };
+
+        match action {
+            Some(Quit) => self.request_quit(),
+            Some(NewTask) => {
                 // Reset wizard data and start the wizard
-                self.wizard_data = TaskWizardData {
-                    title: String::new(),
-                    description: String::new(),
-                    todos: Vec::new(),
-                    current_todo: String::new(),
-                    selected_todo_index: None,
-                    editing_todo_index: None,
-                };
+                self.wizard_data = TaskWizardData::default();
                 self.mode = AppMode::CreateTaskWizard(WizardStep::Title);
             }
-            KeyCode::Char('/') => {
+            Some(QuickAdd) => {
+                self.new_task_title.clear();
+                self.mode = AppMode::CreateTask;
+            }
+            Some(NewFromTemplate) => {
+                self.templates = self.task_manager.list_templates();
+                if self.templates.is_empty() {
+                    let message = format!(
+                        "No templates found in {}",
+                        self.task_manager.templates_dir().display()
+                    );
+                    self.push_notification(Severity::Info, message);
+                } else {
+                    self.template_list_state.select(Some(0));
+                    self.mode = AppMode::TemplatePicker;
+                }
+            }
+            Some(Search) => {
+                self.load_task_bodies();
                 self.mode = AppMode::Search;
-                self.search_query.clear();
+                self.search_input.clear();
+                self.reset_search_selection();
             }
-            KeyCode::Left => match self.focused_pane {
-                FocusedPane::YetToBeDone => {}
-                FocusedPane::InProgress => self.focused_pane = FocusedPane::YetToBeDone,
-                FocusedPane::Completed => self.focused_pane = FocusedPane::InProgress,
-            },
-            KeyCode::Right => match self.focused_pane {
-                FocusedPane::YetToBeDone => self.focused_pane = FocusedPane::InProgress,
-                FocusedPane::InProgress => self.focused_pane = FocusedPane::Completed,
-                FocusedPane::Completed => {}
+            Some(
+                Left | Right | FocusYetToBeDone | FocusInProgress | FocusCompleted
+                | FocusExtraColumn,
+            ) if self.board_mode == BoardMode::Project => {}
+            Some(FocusYetToBeDone) => self.focus_pane(FocusedPane::YetToBeDone),
+            Some(FocusInProgress) => self.focus_pane(FocusedPane::InProgress),
+            Some(FocusCompleted) if self.show_completed => self.focus_pane(FocusedPane::Completed),
+            Some(FocusExtraColumn) => {
+                let column = self
+                    .config
+                    .keybindings
+                    .key_index(FocusExtraColumn, key.code)
+                    .and_then(|index| self.task_manager.columns().get(index));
+                if let Some(column) = column {
+                    self.focus_pane(FocusedPane::Custom(column.name.clone()));
+                }
+            }
+            Some(ToggleCompleted) => {
+                self.show_completed = !self.show_completed;
+                if !self.show_completed && self.focused_pane == FocusedPane::Completed {
+                    self.focus_pane(FocusedPane::InProgress);
+                }
+            }
+            Some(Left) => self.focus_adjacent_pane(-1),
+            Some(Right) => self.focus_adjacent_pane(1),
+            Some(Up) => self.move_selection(-1, self.config.wrap_navigation),
+            Some(Down) => self.move_selection(1, self.config.wrap_navigation),
+            Some(Top) => self.jump_selection(false),
+            Some(Bottom) => self.jump_selection(true),
+            Some(Open) => self.open_selected_task()?,
+            Some(Refresh) => {
+                self.reload_tasks()?;
+            }
+            Some(CycleTagFilter) => self.cycle_tag_filter(),
+            Some(Filter) => {
+                self.load_task_bodies();
+                self.mode = AppMode::Filter;
+            }
+            Some(ClearFilter) if self.filter.is_active() => {
+                self.filter = TaskFilter::default();
+                self.filter_input.clear();
+                self.clamp_list_selections();
+            }
+            Some(ShowParseErrors) if !self.task_manager.parse_errors().is_empty() => {
+                self.parse_error_list_state.select(Some(0));
+                self.mode = AppMode::ParseErrors;
+            }
+            Some(AllTodos) => {
+                self.all_todos_list_state.select(Some(0));
+                self.mode = AppMode::AllTodos;
+            }
+            Some(ToggleAgenda) => {
+                self.filter.agenda = match self.filter.agenda {
+                    Some(_) => None,
+                    None => Some(AgendaWindow {
+                        days: self.config.agenda_days,
+                        include_overdue: true,
+                    }),
+                };
+                self.clamp_list_selections();
+            }
+            Some(ToggleAgendaOverdue) if self.filter.agenda.is_some() => {
+                if let Some(agenda) = &mut self.filter.agenda {
+                    agenda.include_overdue = !agenda.include_overdue;
+                }
+                self.clamp_list_selections();
+            }
+            Some(ToggleBoardMode) => {
+                self.board_mode = match self.board_mode {
+                    BoardMode::Status => BoardMode::Project,
+                    BoardMode::Project => BoardMode::Status,
+                };
+                self.clamp_list_selections();
+            }
+            Some(Duplicate) => {
+                if let Some(task_id) = self.selected_task_id() {
+                    match self.task_manager.duplicate_task(&task_id) {
+                        Ok(copy) => {
+                            let copy_id = copy.id.clone();
+                            self.select_task_on_board(&copy_id);
+                        }
+                        Err(e) => {
+                            self.push_notification(
+                                Severity::Error,
+                                format!("Failed to duplicate task: {}", e),
+                            );
+                        }
+                    }
+                }
+            }
+            Some(Archive) => self.archive_selected_task()?,
+            Some(ArchiveCompleted) => {
+                self.task_manager.archive_completed()?;
+                self.clamp_list_selections();
+            }
+            Some(JumpToTask) => self.open_task_picker(PickerPurpose::Jump),
+            Some(Help) => {
+                self.open_help();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Quits right away when everything is saved, otherwise asks first
+    fn request_quit(&mut self) {
+        if self.task_manager.has_unsaved_changes() {
+            self.mode = AppMode::ConfirmQuit;
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    /// Errors stay until they are dismissed, newest first, and the key that dismisses
+    /// one does nothing else. Other keys are ignored so an error isn't missed while typing.
+    fn handle_error_popup_input(&mut self, key: KeyEvent) {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Enter)
+            && let Some(index) = self
+                .notifications
+                .iter()
+                .rposition(|n| n.severity == Severity::Error)
+        {
+            self.notifications.remove(index);
+        }
+    }
+
+    /// Adds a message to the notification stack
+    pub fn push_notification(&mut self, severity: Severity, message: impl Into<String>) {
+        self.notifications.push(Notification {
+            severity,
+            message: message.into(),
+            created: Instant::now(),
+        });
+    }
+
+    /// Whether an error is waiting to be dismissed, which holds up all other input
+    pub fn has_errors(&self) -> bool {
+        self.notifications
+            .iter()
+            .any(|n| n.severity == Severity::Error)
+    }
+
+    /// Drops notifications that have been up long enough, called by the main loop on
+    /// every tick
+    pub fn expire_notifications(&mut self) {
+        self.notifications.retain(|n| {
+            n.severity
+                .duration()
+                .is_none_or(|duration| n.created.elapsed() < duration)
+        });
+    }
+
+    /// Adds every load warning as an error of its own, so none hides another
+    fn push_load_warnings(&mut self) {
+        for warning in self.task_manager.load_warnings().to_vec() {
+            self.push_notification(Severity::Error, warning);
+        }
+    }
+
+    fn handle_confirm_quit_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => self.should_quit = true,
+            KeyCode::Char('s') | KeyCode::Char('S') => match self.task_manager.save_all() {
+                Ok(()) => self.should_quit = true,
+                Err(e) => {
+                    self.push_notification(Severity::Error, format!("Failed to save tasks: {}", e));
+                    self.mode = AppMode::Dashboard;
+                }
             },
-            KeyCode::Up => self.move_selection(-1),
-            KeyCode::Down => self.move_selection(1),
-            KeyCode::Enter => self.open_selected_task()?,
-            KeyCode::Char('r') => {
-                self.task_manager.load_tasks()?;
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.mode = AppMode::Dashboard;
             }
-            KeyCode::Char('?') => {
-                self.mode = AppMode::Help;
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_parse_errors_input(&mut self, key: KeyEvent) -> Result<()> {
+        use Action::*;
+
+        let action = self
+            .config
+            .keybindings
+            .action(&[Back, ShowParseErrors, Up, Down], key.code);
+
+        let error_count = self.task_manager.parse_errors().len();
+        match action {
+            Some(Back | ShowParseErrors) => self.mode = AppMode::Dashboard,
+            Some(Up) => {
+                let current = self.parse_error_list_state.selected().unwrap_or(0);
+                self.parse_error_list_state
+                    .select(Some(current.saturating_sub(1)));
+            }
+            Some(Down) if error_count > 0 => {
+                let current = self.parse_error_list_state.selected().unwrap_or(0);
+                self.parse_error_list_state
+                    .select(Some((current + 1).min(error_count - 1)));
             }
             _ => {}
         }
         Ok(())
     }
 
-    fn handle_task_detail_input(&mut self, key_code: KeyCode, task_id: &str) -> Result<()> {
-        match key_code {
-            KeyCode::Esc => self.mode = AppMode::Dashboard,
-            KeyCode::Char('q') => self.mode = AppMode::Dashboard,
-            KeyCode::Char('s') => {
+    fn handle_task_detail_input(&mut self, key: KeyEvent, task_id: &str) -> Result<()> {
+        use Action::*;
+
+        if let Some(direction) = Self::half_page_direction(key) {
+            let half_page = (self.todo_list_layout.area.height / 2).max(1) as isize;
+            self.move_todo_selection_by(task_id, direction * half_page);
+            return Ok(());
+        }
+
+        let action = match self.vim_motion(key.code) {
+            VimMotion::Pending => return Ok(()),
+            VimMotion::Jump(action) => Some(action),
+            VimMotion::None => self.config.keybindings.action(
+                &[
+                    Back,
+                    Save,
+                    OpenEditor,
+                    Up,
+                    Down,
+                    PageUp,
+                    PageDown,
+                    Top,
+                    Bottom,
+                    SelectState,
+                    Help,
+                    DeleteTodo,
+                    CompleteAll,
+                    EditTodo,
+                    EditDueDate,
+                    IndentTodo,
+                    OutdentTodo,
+                    JumpToTask,
+                ],
+                key.code,
+            ),
+        };
+
+        match action {
+            Some(Back) => {
+                self.mode = AppMode::Dashboard;
+                // The task may have changed column while it was open
+                self.select_task_on_board(task_id);
+            }
+            Some(Save) => {
                 self.task_manager.save_task(task_id)?;
+                self.push_notification(Severity::Success, "Saved");
             }
-            KeyCode::Up => {
-                // Navigate up in todo list
-                if let Some(task) = self
-                    .task_manager
-                    .get_tasks()
-                    .iter()
-                    .find(|t| t.id == task_id)
-                {
-                    let todo_count = task.todos.len();
-                    if todo_count > 0 {
-                        let current = self.todo_list_state.selected().unwrap_or(0);
-                        let new_index = current.saturating_sub(1);
-                        self.todo_list_state.select(Some(new_index));
+            Some(OpenEditor) => {
+                let editor = std::env::var("EDITOR")
+                    .or_else(|_| std::env::var("VISUAL"))
+                    .ok()
+                    .filter(|editor| !editor.trim().is_empty());
+
+                match editor {
+                    // Reloading after the editor exits would throw the unsaved edits away
+                    Some(_) if self.task_manager.has_unsaved_changes() => {
+                        self.push_notification(
+                            Severity::Error,
+                            "Save your changes before editing the task externally",
+                        );
+                    }
+                    Some(editor) => {
+                        if let Some(task) = self
+                            .task_manager
+                            .get_tasks()
+                            .iter()
+                            .find(|t| t.id == task_id)
+                        {
+                            // Jump to the selected todo if we know where it lives
+                            let line_number = self
+                                .todo_list_state
+                                .selected()
+                                .and_then(|index| task.todos.get(index))
+                                .map(|todo| todo.line_number)
+                                .filter(|line| *line > 0);
+
+                            self.editor_request = Some(EditorRequest {
+                                editor,
+                                file_path: task.file_path.clone(),
+                                line_number,
+                            });
+                        }
+                    }
+                    None => {
+                        self.push_notification(
+                            Severity::Error,
+                            "No editor configured. Set $EDITOR or $VISUAL to edit tasks externally",
+                        );
                     }
                 }
             }
-            KeyCode::Down => {
-                // Navigate down in todo list
-                if let Some(task) = self
+            Some(Up) => self.move_todo_selection(task_id, -1, self.config.wrap_navigation),
+            Some(Down) => self.move_todo_selection(task_id, 1, self.config.wrap_navigation),
+            Some(Top | Bottom) => {
+                let todo_count = self
                     .task_manager
                     .get_tasks()
                     .iter()
                     .find(|t| t.id == task_id)
-                {
-                    let todo_count = task.todos.len();
-                    if todo_count > 0 {
-                        let current = self.todo_list_state.selected().unwrap_or(0);
-                        let new_index = (current + 1).min(todo_count - 1);
-                        self.todo_list_state.select(Some(new_index));
-                    }
+                    .map_or(0, |task| task.todos.len());
+                if todo_count > 0 {
+                    let index = if action == Some(Top) {
+                        0
+                    } else {
+                        todo_count - 1
+                    };
+                    self.todo_list_state.select(Some(index));
                 }
             }
-            KeyCode::Char(' ') => {
+            Some(PageUp) => self.scroll_description(-3),
+            Some(PageDown) => self.scroll_description(3),
+            Some(SelectState) => {
                 // Open TODO state selection dialog
                 if let Some(selected_index) = self.todo_list_state.selected() {
                     self.mode = AppMode::TodoStateSelect {
@@ -214,73 +1156,859 @@ impl App {
                     self.todo_state_list_state.select(Some(0));
                 }
             }
-            KeyCode::Char('?') => {
-                self.mode = AppMode::Help;
+            Some(Help) => {
+                self.open_help();
+            }
+            Some(EditTodo) => {
+                let selected = self.todo_list_state.selected();
+                let todo_text = self
+                    .task_manager
+                    .get_tasks()
+                    .iter()
+                    .find(|t| t.id == task_id)
+                    .and_then(|task| task.todos.get(selected?))
+                    .map(|todo| todo.text.clone());
+                if let (Some(todo_index), Some(todo_text)) = (selected, todo_text) {
+                    self.todo_text_input.set_value(todo_text);
+                    self.mode = AppMode::EditTodo {
+                        task_id: task_id.to_string(),
+                        todo_index,
+                    };
+                }
+            }
+            Some(EditDueDate) => {
+                let due_date = self
+                    .task_manager
+                    .get_tasks()
+                    .iter()
+                    .find(|t| t.id == task_id)
+                    .and_then(|task| task.due_date);
+                self.due_date_input.set_value(
+                    due_date.map_or(String::new(), |due| due.format("%Y-%m-%d").to_string()),
+                );
+                self.mode = AppMode::EditDueDate(task_id.to_string());
+            }
+            Some(CompleteAll) => self.toggle_complete_all(task_id)?,
+            Some(JumpToTask) => self.open_task_picker(PickerPurpose::Jump),
+            Some(IndentTodo | OutdentTodo) => {
+                if let Some(selected_index) = self.todo_list_state.selected() {
+                    let delta = if action == Some(IndentTodo) { 1 } else { -1 };
+                    self.task_manager
+                        .reindent_todo(task_id, selected_index, delta)?;
+                }
+            }
+            Some(DeleteTodo) => {
+                if let Some(selected_index) = self.todo_list_state.selected() {
+                    self.task_manager.remove_todo(task_id, selected_index)?;
+
+                    // Keep the selection within the shrunken list
+                    let todo_count = self
+                        .task_manager
+                        .get_tasks()
+                        .iter()
+                        .find(|t| t.id == task_id)
+                        .map_or(0, |task| task.todos.len());
+                    if todo_count == 0 {
+                        self.todo_list_state.select(None);
+                    } else {
+                        self.todo_list_state
+                            .select(Some(selected_index.min(todo_count - 1)));
+                    }
+                }
+            }
+            _ => match key.code {
+                KeyCode::Char(c @ '1'..='8') => {
+                    // Number keys follow the order of the state selection dialog
+                    let states = TodoState::ALL;
+                    let state_index = c.to_digit(10).unwrap_or(1) as usize - 1;
+                    if let (Some(todo_index), Some(state)) =
+                        (self.todo_list_state.selected(), states.get(state_index))
+                    {
+                        let blockers =
+                            self.task_manager
+                                .set_todo_state(task_id, todo_index, state.clone())?;
+                        if let Some(warning) = Self::blocker_warning(&blockers) {
+                            self.push_notification(Severity::Info, warning);
+                        }
+                    }
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    // Norg state characters, unless the key is bound to an action above
+                    if let (Some(todo_index), Some(state)) = (
+                        self.todo_list_state.selected(),
+                        TodoState::from_norg_char(c),
+                    ) {
+                        let blockers = self
+                            .task_manager
+                            .set_todo_state(task_id, todo_index, state)?;
+                        if let Some(warning) = Self::blocker_warning(&blockers) {
+                            self.push_notification(Severity::Info, warning);
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+        Ok(())
+    }
+
+    /// Moves the focus to another column, selecting its first task if none is selected
+    fn focus_pane(&mut self, pane: FocusedPane) {
+        let category = pane.category();
+        self.focused_pane = pane;
+
+        let has_tasks = self
+            .task_manager
+            .get_tasks_by_category(&self.filter)
+            .get(&category)
+            .is_some_and(|tasks| !tasks.is_empty());
+        if let Some(state) = self.list_states.get_mut(&category)
+            && state.selected().is_none()
+            && has_tasks
+        {
+            state.select(Some(0));
+        }
+    }
+
+    /// The dashboard columns in order: the built-in ones around the configured extra
+    /// columns, without Completed while it is hidden
+    pub fn board_panes(&self) -> Vec<FocusedPane> {
+        let mut panes = vec![FocusedPane::YetToBeDone, FocusedPane::InProgress];
+        panes.extend(
+            self.config
+                .columns
+                .iter()
+                .map(|column| FocusedPane::Custom(column.name.clone())),
+        );
+        if self.show_completed {
+            panes.push(FocusedPane::Completed);
+        }
+        panes
+    }
+
+    /// Moves the focus to the column left (-1) or right (1) of the focused one
+    fn focus_adjacent_pane(&mut self, direction: isize) {
+        let panes = self.board_panes();
+        let adjacent = panes
+            .iter()
+            .position(|pane| *pane == self.focused_pane)
+            .and_then(|index| index.checked_add_signed(direction))
+            .and_then(|index| panes.get(index));
+        if let Some(pane) = adjacent {
+            self.focus_pane(pane.clone());
+        }
+    }
+
+    /// Shows the help popup, which goes back to the current view when closed
+    fn open_help(&mut self) {
+        self.help_return_mode = std::mem::replace(&mut self.mode, AppMode::Help);
+    }
+
+    /// Shows a task in the detail view, starting from the top. The rest of the task is
+    /// read first if only its summary is loaded.
+    fn open_task(&mut self, task_id: String) {
+        if let Err(e) = self.task_manager.load_full(&task_id) {
+            self.push_notification(Severity::Error, format!("Failed to open task: {:#}", e));
+            return;
+        }
+        self.mode = AppMode::TaskDetail(task_id);
+        self.todo_list_state = ListState::default().with_selected(Some(0));
+        self.description_scroll = 0;
+        self.complete_all_undo = None;
+    }
+
+    /// Marks every todo of the task done, or puts the previous states back when pressed
+    /// again right after, as long as the todos are all still done
+    fn toggle_complete_all(&mut self, task_id: &str) -> Result<()> {
+        let all_done = self
+            .task_manager
+            .get_tasks()
+            .iter()
+            .find(|task| task.id == task_id)
+            .is_some_and(|task| task.todos.iter().all(|todo| todo.state == TodoState::Done));
+
+        match self.complete_all_undo.take() {
+            Some((undo_task_id, states)) if undo_task_id == task_id && all_done => {
+                self.task_manager.restore_todo_states(task_id, states)?;
+            }
+            _ => {
+                let (previous, blockers) = self.task_manager.complete_all(task_id)?;
+                let warning = Self::blocker_warning(&blockers);
+                self.complete_all_undo = Some((task_id.to_string(), previous));
+                if let Some(warning) = warning {
+                    self.push_notification(Severity::Info, warning);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Scrolls the description, which is clamped to its length when rendering
+    fn scroll_description(&mut self, lines: i16) {
+        self.description_scroll = self.description_scroll.saturating_add_signed(lines);
+    }
+
+    fn move_todo_selection(&mut self, task_id: &str, direction: i32, wrap: bool) {
+        let todo_count = self
+            .task_manager
+            .get_tasks()
+            .iter()
+            .find(|t| t.id == task_id)
+            .map_or(0, |task| task.todos.len());
+        if todo_count == 0 {
+            return;
+        }
+
+        let current = self.todo_list_state.selected().unwrap_or(0);
+        self.todo_list_state
+            .select(Some(Self::step_index(current, todo_count, direction, wrap)));
+    }
+
+    /// Moves the todo selection by `delta` items, stopping at the ends of the list
+    fn move_todo_selection_by(&mut self, task_id: &str, delta: isize) {
+        let todo_count = self
+            .task_manager
+            .get_tasks()
+            .iter()
+            .find(|t| t.id == task_id)
+            .map_or(0, |task| task.todos.len());
+        if todo_count == 0 {
+            return;
+        }
+
+        let current = self.todo_list_state.selected().unwrap_or(0);
+        self.todo_list_state.select(Some(
+            current.saturating_add_signed(delta).min(todo_count - 1),
+        ));
+    }
+
+    /// `Ctrl-d` and `Ctrl-u` move half a page down or up in long lists
+    fn half_page_direction(key: KeyEvent) -> Option<isize> {
+        if !key.modifiers.contains(KeyModifiers::CONTROL) {
+            return None;
+        }
+        match key.code {
+            KeyCode::Char('d') => Some(1),
+            KeyCode::Char('u') => Some(-1),
+            _ => None,
+        }
+    }
+
+    /// Interprets `gg` and `G` (the `vim_top` and `vim_bottom` keys) when vim navigation
+    /// is on. A `g` waits for a second one and is dropped if another key or nothing
+    /// comes within the timeout.
+    fn vim_motion(&mut self, key_code: KeyCode) -> VimMotion {
+        if !self.config.vim_navigation {
+            return VimMotion::None;
+        }
+
+        let pending = self
+            .pending_g
+            .take()
+            .is_some_and(|pressed| pressed.elapsed() < KEY_SEQUENCE_TIMEOUT);
+        let actions = [Action::VimTop, Action::VimBottom];
+        match self.config.keybindings.action(&actions, key_code) {
+            Some(Action::VimTop) if pending => VimMotion::Jump(Action::Top),
+            Some(Action::VimTop) => {
+                self.pending_g = Some(Instant::now());
+                VimMotion::Pending
+            }
+            Some(Action::VimBottom) => VimMotion::Jump(Action::Bottom),
+            _ => VimMotion::None,
+        }
+    }
+
+    /// The index one step from `current` in a list of `len` items, either stopping at
+    /// the ends or wrapping around to the other one
+    fn step_index(current: usize, len: usize, direction: i32, wrap: bool) -> usize {
+        match (direction > 0, wrap) {
+            (true, true) if current + 1 >= len => 0,
+            (true, _) => (current + 1).min(len - 1),
+            (false, true) if current == 0 => len - 1,
+            (false, _) => current.saturating_sub(1),
+        }
+    }
+
+    /// Runs the requested editor to completion and reloads tasks afterwards.
+    /// The caller is responsible for suspending and restoring the terminal.
+    pub fn open_in_editor(&mut self, request: EditorRequest) -> Result<()> {
+        let mut parts = request.editor.split_whitespace();
+        let program = parts.next().unwrap_or_default();
+
+        let mut command = Command::new(program);
+        command.args(parts);
+        if let Some(line_number) = request.line_number {
+            command.arg(format!("+{}", line_number));
+        }
+        command.arg(&request.file_path);
+
+        match command.status() {
+            Ok(status) if !status.success() => {
+                self.push_notification(Severity::Error, format!("Editor exited with {}", status));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.push_notification(
+                    Severity::Error,
+                    format!("Failed to launch editor '{}': {}", program, e),
+                );
+            }
+        }
+
+        self.reload_tasks()
+    }
+
+    /// Reloads the `.norg` files the watcher saw change since the last check, leaving
+    /// the other tasks alone. Files norgdo wrote itself are skipped. Unsaved edits
+    /// would be lost by a reload, so changes stay queued until they are saved.
+    pub fn reload_on_external_changes(&mut self) -> Result<()> {
+        let written = self.task_manager.take_written_files();
+        if let Some(task_watcher) = &mut self.task_watcher {
+            task_watcher.ignore_own_writes(written);
+        }
+
+        // Changes during a load are picked up once it's done
+        if self.task_manager.has_unsaved_changes() || self.pending_load.is_some() {
+            return Ok(());
+        }
+        let changed = self
+            .task_watcher
+            .as_mut()
+            .map(TaskWatcher::changed_files)
+            .unwrap_or_default();
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        self.reload_keeping_selection(|task_manager| {
+            task_manager.clear_load_warnings();
+            changed
+                .iter()
+                .try_for_each(|path| task_manager.reload_task(path))
+        })
+    }
+
+    /// Reloads all tasks from disk in the background, keeping the current selection and
+    /// open task once they are loaded. Refuses while there are unsaved changes, as the
+    /// reloaded tasks would replace them.
+    pub fn reload_tasks(&mut self) -> Result<()> {
+        if self.task_manager.has_unsaved_changes() {
+            self.push_notification(
+                Severity::Error,
+                "Save your changes before reloading, they would be lost otherwise",
+            );
+            return Ok(());
+        }
+
+        let selection = self.selection();
+        self.start_loading(LoadPurpose::Refresh(selection));
+        Ok(())
+    }
+
+    /// Loads the task summaries on another thread, leaving the current tasks in place
+    /// until `finish_loading` swaps the loaded ones in
+    fn start_loading(&mut self, purpose: LoadPurpose) {
+        let mut task_manager = self.task_manager.unloaded();
+        let (sender, result) = mpsc::channel();
+        std::thread::spawn(move || {
+            let loaded = task_manager.load_summaries().map(|()| task_manager);
+            // The app may have quit in the meantime, then nobody is waiting for it
+            let _ = sender.send(loaded);
+        });
+
+        self.pending_load = Some(PendingLoad {
+            result,
+            purpose,
+            started: Instant::now(),
+        });
+    }
+
+    /// When the tasks started loading in the background, if they are
+    pub fn loading_since(&self) -> Option<Instant> {
+        self.pending_load.as_ref().map(|pending| pending.started)
+    }
+
+    /// Whether the tasks are still being loaded for the first time
+    pub fn is_starting_up(&self) -> bool {
+        self.pending_load
+            .as_ref()
+            .is_some_and(|pending| matches!(pending.purpose, LoadPurpose::Startup(_)))
+    }
+
+    /// Swaps in the tasks loaded by `start_loading` and the bodies loaded by
+    /// `load_task_bodies` if they are ready, called by the main loop on every tick
+    pub fn finish_loading(&mut self) -> Result<()> {
+        self.finish_loading_bodies();
+        let Some(pending) = &self.pending_load else {
+            return Ok(());
+        };
+        let loaded = match pending.result.try_recv() {
+            Ok(loaded) => loaded,
+            Err(TryRecvError::Empty) => return Ok(()),
+            Err(TryRecvError::Disconnected) => Err(color_eyre::eyre::eyre!(
+                "Loading tasks stopped unexpectedly"
+            )),
+        };
+        let Some(pending) = self.pending_load.take() else {
+            return Ok(());
+        };
+
+        // Reloading is refused while there are unsaved changes, so this only guards
+        // against losing them if that ever slips through
+        if self.task_manager.has_unsaved_changes() {
+            self.push_notification(
+                Severity::Error,
+                "Kept the unsaved changes instead of the reloaded tasks",
+            );
+            return Ok(());
+        }
+        self.task_manager = loaded?;
+        match pending.purpose {
+            LoadPurpose::Startup(session) => {
+                self.push_load_warnings();
+                self.restore_session(session);
+                // Columns without a remembered task start at their first one
+                self.clamp_list_selections();
+            }
+            LoadPurpose::Refresh(selection) => self.tasks_reloaded(selection),
+        }
+        // Bodies parsed before the reload may be outdated, so they are parsed again
+        let was_loading_bodies = self.pending_bodies.take().is_some();
+        if was_loading_bodies || !self.filter.query.is_empty() {
+            self.load_task_bodies();
+        }
+        Ok(())
+    }
+
+    /// Fully loads every task on another thread, as searching and filtering look at
+    /// their descriptions. Until `finish_loading_bodies` puts them in, and for tasks
+    /// that can't be read, tasks are matched on their summary.
+    fn load_task_bodies(&mut self) {
+        let summaries = self.task_manager.summaries();
+        if summaries.is_empty() || self.pending_bodies.is_some() {
+            return;
+        }
+
+        let (sender, result) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(TaskManager::parse_full(&summaries));
+        });
+        self.pending_bodies = Some(result);
+    }
+
+    /// Puts in the bodies loaded by `load_task_bodies` if they are ready. Results only
+    /// gain tasks from this, so the selections are kept.
+    fn finish_loading_bodies(&mut self) {
+        let Some(result) = &self.pending_bodies else {
+            return;
+        };
+        let parsed = match result.try_recv() {
+            Ok(parsed) => Ok(parsed),
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(color_eyre::eyre::eyre!(
+                "Loading tasks stopped unexpectedly"
+            )),
+        };
+        self.pending_bodies = None;
+
+        if let Err(e) = parsed.and_then(|parsed| self.task_manager.insert_full(parsed)) {
+            self.push_notification(Severity::Error, format!("Failed to load tasks: {:#}", e));
+        }
+        self.clamp_list_selections();
+        if self.mode == AppMode::Search && self.search_list_state.selected().is_none() {
+            self.reset_search_selection();
+        }
+    }
+
+    /// Runs `reload` on the task manager, then follows the selected tasks and the open
+    /// task to wherever they are now
+    fn reload_keeping_selection(
+        &mut self,
+        reload: impl FnOnce(&mut TaskManager) -> Result<()>,
+    ) -> Result<()> {
+        let selection = self.selection();
+        reload(&mut self.task_manager)?;
+        self.tasks_reloaded(selection);
+        Ok(())
+    }
+
+    /// The tasks currently selected on the dashboard
+    fn selection(&self) -> Selection {
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
+        let column_task_ids = self
+            .list_states
+            .iter()
+            .filter_map(|(category, state)| {
+                let task = tasks_by_category.get(category)?.get(state.selected()?)?;
+                Some((category.clone(), task.id.clone()))
+            })
+            .collect();
+        let project_task_id = self
+            .project_list_state
+            .selected()
+            .and_then(|index| self.project_rows().get(index).cloned().flatten());
+
+        Selection {
+            column_task_ids,
+            project_task_id,
+        }
+    }
+
+    /// Follows the tasks selected before a reload, and the open task, to wherever they
+    /// are now
+    fn tasks_reloaded(&mut self, selection: Selection) {
+        self.push_load_warnings();
+
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
+        for (category, state) in self.list_states.iter_mut() {
+            let tasks = tasks_by_category
+                .get(category)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let index = selection
+                .column_task_ids
+                .get(category)
+                .and_then(|task_id| tasks.iter().position(|t| &t.id == task_id))
+                .or_else(|| {
+                    // The task moved or vanished, so keep the cursor in place instead
+                    state
+                        .selected()
+                        .filter(|_| !tasks.is_empty())
+                        .map(|index| index.min(tasks.len() - 1))
+                });
+            state.select(index);
+        }
+
+        let project_rows = self.project_rows();
+        match selection.project_task_id.and_then(|task_id| {
+            project_rows
+                .iter()
+                .position(|row| row.as_ref() == Some(&task_id))
+        }) {
+            Some(index) => self.project_list_state.select(Some(index)),
+            None => self.clamp_list_selections(),
+        }
+
+        let open_task_id = match &self.mode {
+            AppMode::TaskDetail(task_id)
+            | AppMode::TodoStateSelect { task_id, .. }
+            | AppMode::EditTodo { task_id, .. }
+            | AppMode::EditDueDate(task_id) => Some(task_id.clone()),
+            _ => None,
+        };
+
+        if let Some(task_id) = open_task_id {
+            if let Err(e) = self.task_manager.load_full(&task_id) {
+                self.push_notification(Severity::Error, format!("Failed to open task: {:#}", e));
+            }
+            let todo_count = self
+                .task_manager
+                .get_tasks()
+                .iter()
+                .find(|t| t.id == task_id)
+                .map(|task| task.todos.len());
+
+            match (todo_count, &self.mode) {
+                (
+                    Some(todo_count),
+                    AppMode::TodoStateSelect { todo_index, .. }
+                    | AppMode::EditTodo { todo_index, .. },
+                ) if *todo_index < todo_count => {}
+                (Some(todo_count), _) => {
+                    self.mode = AppMode::TaskDetail(task_id);
+                    if todo_count == 0 {
+                        self.todo_list_state.select(None);
+                    } else if let Some(selected) = self.todo_list_state.selected() {
+                        self.todo_list_state
+                            .select(Some(selected.min(todo_count - 1)));
+                    }
+                }
+                // The open task's file was removed
+                (None, _) => self.mode = AppMode::Dashboard,
+            }
+        }
+    }
+
+    fn handle_search_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::Dashboard,
+            KeyCode::Enter => {
+                // Open the highlighted result, if any
+                let results = self.task_manager.search_tasks(self.search_input.value());
+                if let Some((task, _)) = self
+                    .search_list_state
+                    .selected()
+                    .and_then(|index| results.get(index))
+                {
+                    let task_id = task.id.clone();
+                    self.open_task(task_id);
+                }
+            }
+            KeyCode::Up => {
+                let current = self.search_list_state.selected().unwrap_or(0);
+                self.search_list_state
+                    .select(Some(current.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let result_count = self
+                    .task_manager
+                    .search_tasks(self.search_input.value())
+                    .len();
+                if result_count > 0 {
+                    let current = self.search_list_state.selected().unwrap_or(0);
+                    self.search_list_state
+                        .select(Some((current + 1).min(result_count - 1)));
+                }
+            }
+            key_code => {
+                if self.search_input.handle_key(key_code) {
+                    self.reset_search_selection();
+                }
             }
-            _ => {}
         }
         Ok(())
     }
 
-    fn handle_search_input(&mut self, key_code: KeyCode) -> Result<()> {
-        match key_code {
-            KeyCode::Esc => self.mode = AppMode::Dashboard,
-            KeyCode::Enter => {
-                // TODO: Implement search results view
-                self.mode = AppMode::Dashboard;
+    /// Opens the task picker over the current view. It matches tasks like search does,
+    /// so it starts loading the task bodies.
+    fn open_task_picker(&mut self, purpose: PickerPurpose) {
+        self.load_task_bodies();
+        self.picker_return_mode = std::mem::replace(
+            &mut self.mode,
+            AppMode::TaskPicker {
+                purpose,
+                query: TextInput::default(),
+                selected: 0,
+            },
+        );
+    }
+
+    /// Typing narrows down the tasks, Up/Down choose one of them and Enter picks it
+    fn handle_task_picker_input(
+        &mut self,
+        key: KeyEvent,
+        purpose: PickerPurpose,
+        mut query: TextInput,
+        mut selected: usize,
+    ) -> Result<()> {
+        let matches: Vec<String> = self
+            .task_manager
+            .search_tasks(query.value())
+            .iter()
+            .map(|(task, _)| task.id.clone())
+            .collect();
+        // Fewer tasks may match after a reload
+        selected = selected.min(matches.len().saturating_sub(1));
+
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = std::mem::replace(&mut self.picker_return_mode, AppMode::Dashboard);
+                return Ok(());
+            }
+            KeyCode::Enter => {
+                if let Some(task_id) = matches.get(selected) {
+                    self.task_picked(purpose, task_id.clone());
+                }
+                return Ok(());
+            }
+            KeyCode::Up => selected = selected.saturating_sub(1),
+            KeyCode::Down => selected = (selected + 1).min(matches.len().saturating_sub(1)),
+            key_code => {
+                if query.handle_key(key_code) {
+                    selected = 0;
+                }
+            }
+        }
+
+        self.mode = AppMode::TaskPicker {
+            purpose,
+            query,
+            selected,
+        };
+        Ok(())
+    }
+
+    fn task_picked(&mut self, purpose: PickerPurpose, task_id: String) {
+        match purpose {
+            PickerPurpose::Jump => {
+                // Leaving the task then lands on it on the board
+                self.select_task_on_board(&task_id);
+                self.open_task(task_id);
             }
-            KeyCode::Backspace => {
-                self.search_query.pop();
-            }
-            KeyCode::Char(c) => {
-                self.search_query.push(c);
-            }
-            _ => {}
         }
-        Ok(())
     }
 
-    fn handle_help_input(&mut self, key_code: KeyCode) -> Result<()> {
-        match key_code {
-            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
-                self.mode = AppMode::Dashboard;
-                self.help_scroll_offset = 0; // Reset scroll when closing help
+    /// Selects the first search result, or nothing when there are no matches
+    fn reset_search_selection(&mut self) {
+        let has_results = !self
+            .task_manager
+            .search_tasks(self.search_input.value())
+            .is_empty();
+        self.search_list_state
+            .select(if has_results { Some(0) } else { None });
+    }
+
+    fn handle_help_input(&mut self, key: KeyEvent) -> Result<()> {
+        use Action::*;
+
+        if let Some(direction) = Self::half_page_direction(key) {
+            let half_page = (self.help_page_height / 2).max(1) as i16;
+            self.scroll_help(direction as i16 * half_page);
+            return Ok(());
+        }
+
+        let action = self.config.keybindings.action(
+            &[
+                Back,
+                Help,
+                Up,
+                Down,
+                PageUp,
+                PageDown,
+                Top,
+                Bottom,
+                NextSection,
+                PrevSection,
+            ],
+            key.code,
+        );
+
+        let (_, section_starts) = help::help_content(self.config.auto_save);
+        let offset = self.help_list_state.offset();
+        match action {
+            Some(Back | Help) => {
+                self.mode = std::mem::replace(&mut self.help_return_mode, AppMode::Dashboard);
+                self.help_list_state = ListState::default(); // Reset scroll when closing help
+                self.help_section = 0;
                 self.help_scrollbar_state = ScrollbarState::default(); // Reset scrollbar state
             }
-            KeyCode::Up => {
-                if self.help_scroll_offset > 0 {
-                    self.help_scroll_offset -= 1;
-                }
-            }
-            KeyCode::Down => {
-                // We'll clamp this in the UI render function based on content size
-                self.help_scroll_offset += 1;
+            Some(Up) => self.scroll_help(-1),
+            Some(Down) => self.scroll_help(1),
+            Some(PageUp) => {
+                let section = section_starts
+                    .iter()
+                    .rposition(|&start| start < offset)
+                    .unwrap_or(0);
+                self.show_help_section(section, &section_starts);
             }
-            KeyCode::PageUp => {
-                self.help_scroll_offset = self.help_scroll_offset.saturating_sub(5);
+            Some(PageDown) => {
+                let section = section_starts
+                    .iter()
+                    .position(|&start| start > offset)
+                    .unwrap_or(section_starts.len() - 1);
+                self.show_help_section(section, &section_starts);
             }
-            KeyCode::PageDown => {
-                self.help_scroll_offset += 5;
+            Some(NextSection) => {
+                let section = (self.help_section + 1) % section_starts.len();
+                self.show_help_section(section, &section_starts);
             }
-            KeyCode::Home => {
-                self.help_scroll_offset = 0;
+            Some(PrevSection) => {
+                let section = self
+                    .help_section
+                    .checked_sub(1)
+                    .unwrap_or(section_starts.len() - 1);
+                self.show_help_section(section, &section_starts);
             }
-            KeyCode::End => {
-                self.help_scroll_offset = u16::MAX; // Set to max to scroll to bottom
+            Some(Top) => self.show_help_section(0, &section_starts),
+            Some(Bottom) => {
+                *self.help_list_state.offset_mut() = self.help_max_scroll;
+                self.help_section = section_starts.len() - 1;
             }
             _ => {}
         }
         Ok(())
     }
 
-    fn move_selection(&mut self, direction: i32) {
-        let current_category = match self.focused_pane {
-            FocusedPane::YetToBeDone => KanbanCategory::YetToBeDone,
-            FocusedPane::InProgress => KanbanCategory::InProgress,
-            FocusedPane::Completed => KanbanCategory::Completed,
+    /// Scrolls the help, stopping once its last line is at the bottom
+    fn scroll_help(&mut self, lines: i16) {
+        let offset = self
+            .help_list_state
+            .offset()
+            .saturating_add_signed(lines as isize)
+            .min(self.help_max_scroll);
+        *self.help_list_state.offset_mut() = offset;
+        let (_, section_starts) = help::help_content(self.config.auto_save);
+        self.help_section = section_starts
+            .iter()
+            .rposition(|&start| start <= offset)
+            .unwrap_or(0);
+    }
+
+    /// Scrolls the help so the section's title is at the top. The offset may be
+    /// clamped near the end, so the section is remembered for cycling with Tab.
+    fn show_help_section(&mut self, section: usize, section_starts: &[usize]) {
+        self.help_section = section;
+        *self.help_list_state.offset_mut() = section_starts[section].min(self.help_max_scroll);
+    }
+
+    fn handle_filter_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => {
+                self.filter_input.clear();
+                self.mode = AppMode::Dashboard;
+            }
+            KeyCode::Enter => self.mode = AppMode::Dashboard,
+            key_code => {
+                self.filter_input.handle_key(key_code);
+            }
+        }
+        // The filter applies live, so keep selections valid as the visible set changes
+        self.filter.query = self.filter_input.value().to_string();
+        self.clamp_list_selections();
+        Ok(())
+    }
+
+    /// Steps the tag filter through every known tag, then back to showing all tasks
+    fn cycle_tag_filter(&mut self) {
+        let tags = self.task_manager.all_tags();
+        let next_index = match &self.filter.tag {
+            Some(current) => tags
+                .iter()
+                .position(|tag| tag == current)
+                .map_or(0, |index| index + 1),
+            None => 0,
         };
+        self.filter.tag = tags.get(next_index).map(|tag| tag.to_string());
+        self.clamp_list_selections();
+    }
+
+    /// Keeps each column's selection within its (possibly filtered) task count
+    fn clamp_list_selections(&mut self) {
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
+        for (category, state) in self.list_states.iter_mut() {
+            let task_count = tasks_by_category.get(category).map_or(0, Vec::len);
+            // Each column keeps its own selection, and one that just got tasks starts
+            // at the top so moving the focus there always lands on a task
+            if task_count == 0 {
+                state.select(None);
+            } else {
+                let selected = state.selected().unwrap_or(0);
+                state.select(Some(selected.min(task_count - 1)));
+            }
+        }
+
+        // Keep the project view on a task row, the nearest one to the old selection
+        let rows = self.project_rows();
+        let selected = self.project_list_state.selected().unwrap_or(0);
+        let task_row = (selected.min(rows.len())..rows.len())
+            .find(|index| rows[*index].is_some())
+            .or_else(|| {
+                (0..selected.min(rows.len()))
+                    .rev()
+                    .find(|index| rows[*index].is_some())
+            });
+        self.project_list_state.select(task_row);
+    }
+
+    fn move_selection(&mut self, direction: i32, wrap: bool) {
+        if self.board_mode == BoardMode::Project {
+            self.move_project_selection(direction, wrap);
+            return;
+        }
+
+        let current_category = self.focused_pane.category();
 
-        let tasks_by_category = self.task_manager.get_tasks_by_category();
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
         let tasks_in_category = tasks_by_category
             .get(&current_category)
             .map(|v| v.len())
@@ -292,119 +2020,298 @@ impl App {
 
         let state = self.list_states.get_mut(&current_category).unwrap();
         let current = state.selected().unwrap_or(0);
+        state.select(Some(Self::step_index(
+            current,
+            tasks_in_category,
+            direction,
+            wrap,
+        )));
+    }
+
+    /// Rows of the project view in display order: `None` for a lane header, followed
+    /// by the ids of the tasks in that lane
+    pub fn project_rows(&self) -> Vec<Option<String>> {
+        self.task_manager
+            .get_tasks_by_project(&self.filter)
+            .into_iter()
+            .flat_map(|(_, tasks)| {
+                std::iter::once(None).chain(tasks.into_iter().map(|task| Some(task.id.clone())))
+            })
+            .collect()
+    }
+
+    /// Selects the first or last task of the focused column or the project view
+    fn jump_selection(&mut self, to_last: bool) {
+        if self.board_mode == BoardMode::Project {
+            let rows = self.project_rows();
+            let mut tasks = (0..rows.len()).filter(|index| rows[*index].is_some());
+            let index = if to_last {
+                tasks.next_back()
+            } else {
+                tasks.next()
+            };
+            if index.is_some() {
+                self.project_list_state.select(index);
+            }
+            return;
+        }
+
+        let category = self.focused_pane.category();
+        let task_count = self
+            .task_manager
+            .get_tasks_by_category(&self.filter)
+            .get(&category)
+            .map_or(0, Vec::len);
+        if task_count > 0
+            && let Some(state) = self.list_states.get_mut(&category)
+        {
+            state.select(Some(if to_last { task_count - 1 } else { 0 }));
+        }
+    }
 
-        let new_index = if direction > 0 {
-            (current + 1).min(tasks_in_category - 1)
+    /// Moves to the next task row in the project view, skipping lane headers
+    fn move_project_selection(&mut self, direction: i32, wrap: bool) {
+        let rows = self.project_rows();
+        let current = self.project_list_state.selected();
+        let is_task = |index: &usize| rows[*index].is_some();
+        let next = if direction > 0 {
+            let start = current.map_or(0, |index| index + 1);
+            (start..rows.len())
+                .find(is_task)
+                .or_else(|| (0..rows.len()).find(is_task).filter(|_| wrap))
         } else {
-            current.saturating_sub(1)
+            let end = current.unwrap_or(0);
+            (0..end)
+                .rev()
+                .find(is_task)
+                .or_else(|| (0..rows.len()).rev().find(is_task).filter(|_| wrap))
         };
+        if next.is_some() {
+            self.project_list_state.select(next);
+        }
+    }
+
+    /// Focuses the column a task is in and selects it there (and in the project view),
+    /// if the filter shows it
+    fn select_task_on_board(&mut self, task_id: &str) {
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
+        let found = tasks_by_category.iter().find_map(|(category, tasks)| {
+            let index = tasks.iter().position(|task| task.id == task_id)?;
+            Some((category.clone(), index))
+        });
+
+        if let Some((category, index)) = found {
+            // A hidden Completed column keeps its selection but never gets the focus
+            if category != KanbanCategory::Completed || self.show_completed {
+                self.focused_pane = FocusedPane::from_category(&category);
+            }
+            if let Some(state) = self.list_states.get_mut(&category) {
+                state.select(Some(index));
+            }
+        }
+        if let Some(row) = self
+            .project_rows()
+            .iter()
+            .position(|row| row.as_deref() == Some(task_id))
+        {
+            self.project_list_state.select(Some(row));
+        }
+        self.clamp_list_selections();
+    }
+
+    /// Id of the task selected in the focused column (or the project view), if any
+    fn selected_task_id(&self) -> Option<String> {
+        if self.board_mode == BoardMode::Project {
+            let selected = self.project_list_state.selected()?;
+            return self.project_rows().get(selected).cloned().flatten();
+        }
 
-        state.select(Some(new_index));
+        let category = self.focused_pane.category();
+        let selected = self.list_states.get(&category)?.selected()?;
+        let tasks_by_category = self.task_manager.get_tasks_by_category(&self.filter);
+        let task = tasks_by_category.get(&category)?.get(selected)?;
+        Some(task.id.clone())
     }
 
     fn open_selected_task(&mut self) -> Result<()> {
-        let current_category = match self.focused_pane {
-            FocusedPane::YetToBeDone => KanbanCategory::YetToBeDone,
-            FocusedPane::InProgress => KanbanCategory::InProgress,
-            FocusedPane::Completed => KanbanCategory::Completed,
+        if let Some(task_id) = self.selected_task_id() {
+            self.open_task(task_id);
+        }
+        Ok(())
+    }
+
+    /// Moves the selected task to the archive, as long as it is completed
+    fn archive_selected_task(&mut self) -> Result<()> {
+        let Some(task_id) = self.selected_task_id() else {
+            return Ok(());
         };
 
-        let tasks_by_category = self.task_manager.get_tasks_by_category();
-        if let Some(tasks) = tasks_by_category.get(&current_category) {
-            if let Some(state) = self.list_states.get(&current_category) {
-                if let Some(selected) = state.selected() {
-                    if let Some(task) = tasks.get(selected) {
-                        self.mode = AppMode::TaskDetail(task.id.clone());
-                        // Reset todo list state when entering task detail
-                        self.todo_list_state.select(Some(0));
+        let is_completed = self.task_manager.get_tasks().iter().any(|task| {
+            task.id == task_id && self.task_manager.category_of(task) == KanbanCategory::Completed
+        });
+        if !is_completed {
+            self.push_notification(Severity::Info, "Only completed tasks can be archived");
+            return Ok(());
+        }
+        self.task_manager.archive_task(&task_id)?;
+        self.clamp_list_selections();
+        Ok(())
+    }
+
+    /// Quick-add prompt: creates a task with just a title, without going through the wizard
+    fn handle_create_task_input(&mut self, key: KeyEvent, template: Option<&str>) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::Dashboard,
+            KeyCode::Enter => {
+                let title = self.new_task_title.value().trim().to_string();
+                if title.is_empty() {
+                    return Ok(());
+                }
+
+                let created = match template {
+                    Some(template) => self.task_manager.create_from_template(template, title),
+                    None => self.task_manager.create_task(title),
+                };
+                match created {
+                    Ok(task) => {
+                        let task_id = task.id.clone();
+                        self.select_task_on_board(&task_id);
                     }
+                    Err(e) => self.push_notification(
+                        Severity::Error,
+                        format!("Failed to create task: {}", e),
+                    ),
+                }
+                self.new_task_title.clear();
+                self.mode = AppMode::Dashboard;
+            }
+            key_code => {
+                self.new_task_title.handle_key(key_code);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_template_picker_input(&mut self, key: KeyEvent) -> Result<()> {
+        use Action::*;
+
+        let action = self
+            .config
+            .keybindings
+            .action(&[Back, Up, Down, Open], key.code);
+
+        match action {
+            Some(Back) => self.mode = AppMode::Dashboard,
+            Some(Up) => {
+                let current = self.template_list_state.selected().unwrap_or(0);
+                self.template_list_state
+                    .select(Some(current.saturating_sub(1)));
+            }
+            Some(Down) if !self.templates.is_empty() => {
+                let current = self.template_list_state.selected().unwrap_or(0);
+                self.template_list_state
+                    .select(Some((current + 1).min(self.templates.len() - 1)));
+            }
+            Some(Open) => {
+                let selected = self.template_list_state.selected().unwrap_or(0);
+                if let Some(template) = self.templates.get(selected) {
+                    self.new_task_title.clear();
+                    self.mode = AppMode::CreateFromTemplate(template.clone());
                 }
             }
+            _ => {}
         }
         Ok(())
     }
 
-    fn handle_wizard_input(&mut self, key_code: KeyCode, step: WizardStep) -> Result<()> {
+    fn handle_wizard_input(&mut self, key: KeyEvent, step: WizardStep) -> Result<()> {
         match step {
-            WizardStep::Title => self.handle_wizard_title_input(key_code)?,
-            WizardStep::Description => self.handle_wizard_description_input(key_code)?,
-            WizardStep::Todos => self.handle_wizard_todos_input(key_code)?,
-            WizardStep::Confirm => self.handle_wizard_confirm_input(key_code)?,
+            WizardStep::Title => self.handle_wizard_title_input(key)?,
+            WizardStep::Description => self.handle_wizard_description_input(key)?,
+            WizardStep::DueDate => self.handle_wizard_due_date_input(key)?,
+            WizardStep::Todos => self.handle_wizard_todos_input(key)?,
+            WizardStep::Confirm => self.handle_wizard_confirm_input(key)?,
         }
         Ok(())
     }
 
-    fn handle_wizard_title_input(&mut self, key_code: KeyCode) -> Result<()> {
-        match key_code {
-            KeyCode::Esc => self.mode = AppMode::Dashboard,
+    fn handle_wizard_title_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.cancel_wizard(WizardStep::Title),
             KeyCode::Enter => {
-                if !self.wizard_data.title.trim().is_empty() {
+                if !self.wizard_data.title.value().trim().is_empty() {
                     self.mode = AppMode::CreateTaskWizard(WizardStep::Description);
                 }
             }
-            KeyCode::Backspace => {
-                self.wizard_data.title.pop();
-            }
-            KeyCode::Char(c) => {
-                self.wizard_data.title.push(c);
+            key_code => {
+                // Title is the first step, so Left only ever moves the cursor
+                self.wizard_data.title.handle_key(key_code);
             }
-            _ => {}
         }
         Ok(())
     }
 
-    fn handle_wizard_description_input(&mut self, key_code: KeyCode) -> Result<()> {
-        match key_code {
-            KeyCode::Esc => self.mode = AppMode::Dashboard,
-            KeyCode::Enter => {
-                // Move to todos step regardless of description content
-                self.mode = AppMode::CreateTaskWizard(WizardStep::Todos);
+    fn handle_wizard_description_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.cancel_wizard(WizardStep::Description),
+            KeyCode::Enter
+                if key
+                    .modifiers
+                    .intersects(KeyModifiers::ALT | KeyModifiers::CONTROL) =>
+            {
+                // Plain Enter moves on, so a new line needs a modifier
+                self.wizard_data.description.insert_char('\n');
             }
-            KeyCode::Backspace => {
-                self.wizard_data.description.pop();
+            KeyCode::Enter => {
+                // Move to due date step regardless of description content
+                self.mode = AppMode::CreateTaskWizard(WizardStep::DueDate);
             }
-            KeyCode::Left => {
-                // Explicit back navigation using Left arrow
+            KeyCode::Backspace | KeyCode::Left if self.wizard_data.description.is_at_start() => {
+                // Nothing before the cursor, go back to title step
                 self.mode = AppMode::CreateTaskWizard(WizardStep::Title);
             }
-            KeyCode::Char(c) => {
-                self.wizard_data.description.push(c);
+            key_code => {
+                self.wizard_data.description.handle_key(key_code);
             }
-            _ => {}
         }
         Ok(())
     }
 
-    fn handle_wizard_todos_input(&mut self, key_code: KeyCode) -> Result<()> {
-        match key_code {
-            KeyCode::Esc => {
-                // Clear editing state when canceling
-                self.wizard_data.editing_todo_index = None;
-                self.wizard_data.current_todo.clear();
-                self.mode = AppMode::Dashboard;
+    fn handle_wizard_due_date_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.cancel_wizard(WizardStep::DueDate),
+            KeyCode::Enter => {
+                // Empty skips the step, input that isn't a date is pointed out while typing
+                if self.wizard_data.parsed_due_date().is_ok() {
+                    self.mode = AppMode::CreateTaskWizard(WizardStep::Todos);
+                }
             }
+            KeyCode::Backspace | KeyCode::Left if self.wizard_data.due_date.is_at_start() => {
+                // Nothing before the cursor, go back to description step
+                self.mode = AppMode::CreateTaskWizard(WizardStep::Description);
+            }
+            key_code => {
+                self.wizard_data.due_date.handle_key(key_code);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_wizard_todos_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Cycle the initial state of the todo being typed
+                let states = TodoState::ALL;
+                let current = states
+                    .iter()
+                    .position(|state| *state == self.wizard_data.current_todo_state)
+                    .unwrap_or(0);
+                self.wizard_data.current_todo_state = states[(current + 1) % states.len()].clone();
+            }
+            KeyCode::Esc => self.cancel_wizard(WizardStep::Todos),
             KeyCode::Enter => {
-                if !self.wizard_data.current_todo.trim().is_empty() {
-                    // Check if we're editing an existing TODO
-                    if let Some(edit_index) = self.wizard_data.editing_todo_index {
-                        // Replace the TODO at the original position
-                        if edit_index < self.wizard_data.todos.len() {
-                            self.wizard_data.todos[edit_index] =
-                                self.wizard_data.current_todo.clone();
-                        } else {
-                            // Fallback: add to end if index is invalid
-                            self.wizard_data
-                                .todos
-                                .push(self.wizard_data.current_todo.clone());
-                        }
-                        self.wizard_data.editing_todo_index = None;
-                    } else {
-                        // Add new TODO to the end
-                        self.wizard_data
-                            .todos
-                            .push(self.wizard_data.current_todo.clone());
-                    }
-                    self.wizard_data.current_todo.clear();
+                if !self.wizard_data.current_todo.value().trim().is_empty() {
+                    self.commit_wizard_todo();
                     self.wizard_data.selected_todo_index = None;
                 } else {
                     // If current todo is empty, move to confirm step
@@ -412,49 +2319,36 @@ impl App {
                 }
             }
             KeyCode::Backspace => {
-                self.wizard_data.current_todo.pop();
+                if self.wizard_data.current_todo.is_empty()
+                    && self.wizard_data.editing_todo_index.is_none()
+                {
+                    // Nothing left to delete, go back to due date step
+                    self.mode = AppMode::CreateTaskWizard(WizardStep::DueDate);
+                    return Ok(());
+                }
+
+                self.wizard_data.current_todo.backspace();
                 // If we've cleared the input while editing, cancel the edit
                 if self.wizard_data.current_todo.is_empty()
                     && self.wizard_data.editing_todo_index.is_some()
                 {
-                    self.wizard_data.editing_todo_index = None;
+                    self.cancel_wizard_todo_edit();
                 }
             }
-            KeyCode::Char(c) => {
-                self.wizard_data.current_todo.push(c);
-            }
             KeyCode::Tab => {
-                // Skip to confirm step
-                if !self.wizard_data.current_todo.trim().is_empty() {
-                    // Check if we're editing an existing TODO
-                    if let Some(edit_index) = self.wizard_data.editing_todo_index {
-                        // Replace the TODO at the original position
-                        if edit_index < self.wizard_data.todos.len() {
-                            self.wizard_data.todos[edit_index] =
-                                self.wizard_data.current_todo.clone();
-                        } else {
-                            // Fallback: add to end if index is invalid
-                            self.wizard_data
-                                .todos
-                                .push(self.wizard_data.current_todo.clone());
-                        }
-                        self.wizard_data.editing_todo_index = None;
-                    } else {
-                        // Add new TODO to the end
-                        self.wizard_data
-                            .todos
-                            .push(self.wizard_data.current_todo.clone());
-                    }
-                    self.wizard_data.current_todo.clear();
-                }
-                self.mode = AppMode::CreateTaskWizard(WizardStep::Confirm);
+                // Nest the todo being typed under the one before it
+                let max_indent = self.wizard_todo_max_indent();
+                self.wizard_data.current_todo_indent =
+                    (self.wizard_data.current_todo_indent + 1).min(max_indent);
             }
-            KeyCode::Left => {
-                // Clear editing state when going back
-                self.wizard_data.editing_todo_index = None;
-                self.wizard_data.current_todo.clear();
-                // Go back to description step
-                self.mode = AppMode::CreateTaskWizard(WizardStep::Description);
+            KeyCode::BackTab => {
+                self.wizard_data.current_todo_indent =
+                    self.wizard_data.current_todo_indent.saturating_sub(1);
+            }
+            KeyCode::Left if self.wizard_data.current_todo.is_at_start() => {
+                // Go back to due date step, keeping whatever is being typed so it's
+                // still there when coming back
+                self.mode = AppMode::CreateTaskWizard(WizardStep::DueDate);
             }
             KeyCode::Up => {
                 // Navigate up in TODO list
@@ -487,67 +2381,133 @@ impl App {
                     }
                 }
             }
-            KeyCode::Delete => {
-                // Delete selected TODO item
-                if let Some(index) = self.wizard_data.selected_todo_index {
-                    if index < self.wizard_data.todos.len() {
-                        self.wizard_data.todos.remove(index);
-                        // Adjust selection after deletion
-                        if self.wizard_data.todos.is_empty() {
-                            self.wizard_data.selected_todo_index = None;
-                        } else if index >= self.wizard_data.todos.len() {
-                            self.wizard_data.selected_todo_index =
-                                Some(self.wizard_data.todos.len() - 1);
+            KeyCode::Delete if self.wizard_data.selected_todo_index.is_some() => {
+                // Delete selected TODO item, otherwise Delete edits the input
+                if let Some(index) = self.wizard_data.selected_todo_index
+                    && index < self.wizard_data.todos.len()
+                {
+                    // Nested items go with their parent, same as in the task detail view
+                    let level = self.wizard_data.todos[index].level;
+                    let end = self.wizard_data.todos[index + 1..]
+                        .iter()
+                        .position(|child| child.level <= level)
+                        .map_or(self.wizard_data.todos.len(), |offset| index + 1 + offset);
+                    self.wizard_data.todos.drain(index..end);
+
+                    // Keep the item being edited pointing at the right entry
+                    match self.wizard_data.editing_todo_index {
+                        Some(edit_index) if (index..end).contains(&edit_index) => {
+                            self.cancel_wizard_todo_edit();
                         }
+                        Some(edit_index) if edit_index >= end => {
+                            self.wizard_data.editing_todo_index = Some(edit_index - (end - index));
+                        }
+                        _ => {}
+                    }
+
+                    // Adjust selection after deletion
+                    if self.wizard_data.todos.is_empty() {
+                        self.wizard_data.selected_todo_index = None;
+                    } else if index >= self.wizard_data.todos.len() {
+                        self.wizard_data.selected_todo_index =
+                            Some(self.wizard_data.todos.len() - 1);
                     }
                 }
             }
             KeyCode::F(2) => {
                 // Edit selected TODO item (copy to current input)
-                if let Some(index) = self.wizard_data.selected_todo_index {
-                    if index < self.wizard_data.todos.len() {
-                        self.wizard_data.current_todo = self.wizard_data.todos[index].clone();
-                        self.wizard_data.editing_todo_index = Some(index);
-                        self.wizard_data.selected_todo_index = None;
-                    }
+                if let Some(index) = self.wizard_data.selected_todo_index
+                    && index < self.wizard_data.todos.len()
+                {
+                    let todo = &self.wizard_data.todos[index];
+                    self.wizard_data.current_todo.set_value(todo.text.clone());
+                    self.wizard_data.current_todo_indent = todo.level.saturating_sub(1);
+                    self.wizard_data.current_todo_state = todo.state.clone();
+                    self.wizard_data.editing_todo_index = Some(index);
+                    self.wizard_data.selected_todo_index = None;
                 }
             }
-            _ => {}
+            key_code => {
+                self.wizard_data.current_todo.handle_key(key_code);
+            }
         }
         Ok(())
     }
 
-    fn handle_wizard_confirm_input(&mut self, key_code: KeyCode) -> Result<()> {
-        match key_code {
-            KeyCode::Esc => self.mode = AppMode::Dashboard,
+    /// Adds the todo being typed to the wizard list, or replaces the one being edited.
+    /// The text is trimmed, and a todo that is only whitespace is not added.
+    fn commit_wizard_todo(&mut self) {
+        let text = self.wizard_data.current_todo.value().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let todo = TodoItem::new(
+            text,
+            std::mem::take(&mut self.wizard_data.current_todo_state),
+            self.wizard_data.current_todo_indent + 1,
+        );
+
+        match self.wizard_data.editing_todo_index.take() {
+            // Replace the TODO at the original position
+            Some(edit_index) if edit_index < self.wizard_data.todos.len() => {
+                self.wizard_data.todos[edit_index] = todo;
+            }
+            // Add new TODO to the end
+            _ => self.wizard_data.todos.push(todo),
+        }
+        self.wizard_data.current_todo.clear();
+    }
+
+    /// Drops the todo being typed or edited, going back to adding a new top-level todo
+    fn cancel_wizard_todo_edit(&mut self) {
+        self.wizard_data.editing_todo_index = None;
+        self.wizard_data.current_todo.clear();
+        self.wizard_data.current_todo_indent = 0;
+        self.wizard_data.current_todo_state = TodoState::default();
+    }
+
+    /// A todo can be nested at most one level deeper than the todo right before it
+    fn wizard_todo_max_indent(&self) -> usize {
+        let previous = match self.wizard_data.editing_todo_index {
+            Some(edit_index) => edit_index
+                .checked_sub(1)
+                .and_then(|index| self.wizard_data.todos.get(index)),
+            None => self.wizard_data.todos.last(),
+        };
+        // Levels are 1-based, so the previous level is exactly one indent deeper
+        previous.map_or(0, |todo| todo.level)
+    }
+
+    fn handle_wizard_confirm_input(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.cancel_wizard(WizardStep::Confirm);
+            }
             KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                // Already checked when leaving the due date step
+                let due_date = self.wizard_data.parsed_due_date().unwrap_or_default();
                 // Create the task
                 match self.task_manager.create_task_with_details(
-                    self.wizard_data.title.clone(),
-                    self.wizard_data.description.clone(),
+                    self.wizard_data.title.value().to_string(),
+                    self.wizard_data.description.value().to_string(),
+                    due_date,
                     self.wizard_data.todos.clone(),
                 ) {
                     Ok(_) => {
                         self.mode = AppMode::Dashboard;
                         // Reset wizard data
-                        self.wizard_data = TaskWizardData {
-                            title: String::new(),
-                            description: String::new(),
-                            todos: Vec::new(),
-                            current_todo: String::new(),
-                            selected_todo_index: None,
-                            editing_todo_index: None,
-                        };
+                        self.wizard_data = TaskWizardData::default();
+                        self.push_notification(Severity::Success, "Task created");
                     }
                     Err(e) => {
-                        self.error_message = Some(format!("Failed to create task: {}", e));
+                        self.push_notification(
+                            Severity::Error,
+                            format!("Failed to create task: {}", e),
+                        );
                         self.mode = AppMode::Dashboard;
                     }
                 }
             }
-            KeyCode::Char('n') | KeyCode::Char('N') => {
-                self.mode = AppMode::Dashboard;
-            }
             KeyCode::Left | KeyCode::Backspace => {
                 // Go back to TODOs step
                 self.mode = AppMode::CreateTaskWizard(WizardStep::Todos);
@@ -557,36 +2517,214 @@ impl App {
         Ok(())
     }
 
+    /// Whether the title typed in the wizard is already taken by a task, ignoring case
+    /// and surrounding whitespace
+    pub fn wizard_title_exists(&self) -> bool {
+        let title = self.wizard_data.title.value().trim().to_lowercase();
+        !title.is_empty()
+            && self
+                .task_manager
+                .get_tasks()
+                .iter()
+                .any(|task| task.title.trim().to_lowercase() == title)
+    }
+
+    /// Leaves the wizard, asking first if anything was entered, as it would be lost
+    fn cancel_wizard(&mut self, step: WizardStep) {
+        if self.wizard_data.is_empty() {
+            self.wizard_data = TaskWizardData::default();
+            self.mode = AppMode::Dashboard;
+        } else {
+            self.mode = AppMode::ConfirmDiscardWizard(step);
+        }
+    }
+
+    fn handle_confirm_discard_wizard_input(
+        &mut self,
+        key: KeyEvent,
+        step: WizardStep,
+    ) -> Result<()> {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.wizard_data = TaskWizardData::default();
+                self.mode = AppMode::Dashboard;
+            }
+            // Anything else keeps editing, the default answer is no
+            _ => self.mode = AppMode::CreateTaskWizard(step),
+        }
+        Ok(())
+    }
+
+    fn handle_all_todos_input(&mut self, key: KeyEvent) -> Result<()> {
+        use Action::*;
+
+        let action = self.config.keybindings.action(
+            &[Back, Up, Down, Top, Bottom, Open, SelectState, Filter],
+            key.code,
+        );
+
+        let rows: Vec<(String, usize)> = self
+            .task_manager
+            .all_todos(self.all_todos_state_filter.as_ref())
+            .into_iter()
+            .map(|(task, index)| (task.id.clone(), index))
+            .collect();
+        let selected = self.all_todos_list_state.selected().unwrap_or(0);
+
+        match action {
+            Some(Back) => self.mode = AppMode::Dashboard,
+            Some(direction @ (Up | Down)) if !rows.is_empty() => {
+                let direction = if direction == Up { -1 } else { 1 };
+                let wrap = self.config.wrap_navigation;
+                self.all_todos_list_state.select(Some(Self::step_index(
+                    selected,
+                    rows.len(),
+                    direction,
+                    wrap,
+                )));
+            }
+            Some(Top) => self.all_todos_list_state.select(Some(0)),
+            Some(Bottom) => self
+                .all_todos_list_state
+                .select(Some(rows.len().saturating_sub(1))),
+            Some(Open) => {
+                if let Some((task_id, todo_index)) = rows.get(selected) {
+                    let todo_index = *todo_index;
+                    self.open_task(task_id.clone());
+                    self.todo_list_state.select(Some(todo_index));
+                }
+            }
+            Some(SelectState) => {
+                if let Some((task_id, todo_index)) = rows.get(selected) {
+                    let blockers = self.task_manager.toggle_todo_state(task_id, *todo_index)?;
+                    if let Some(warning) = Self::blocker_warning(&blockers) {
+                        self.push_notification(Severity::Info, warning);
+                    }
+
+                    // The todo may have left the list if it is filtered by state
+                    let row_count = self
+                        .task_manager
+                        .all_todos(self.all_todos_state_filter.as_ref())
+                        .len();
+                    self.all_todos_list_state
+                        .select(Some(selected.min(row_count.saturating_sub(1))));
+                }
+            }
+            Some(Filter) => {
+                // Cycle through the states in the order of the state selection dialog
+                let states = TodoState::ALL;
+                self.all_todos_state_filter = match &self.all_todos_state_filter {
+                    None => states.first().cloned(),
+                    Some(current) => states
+                        .iter()
+                        .position(|state| state == current)
+                        .and_then(|index| states.get(index + 1))
+                        .cloned(),
+                };
+                self.all_todos_list_state.select(Some(0));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_edit_todo_input(
+        &mut self,
+        key: KeyEvent,
+        task_id: &str,
+        todo_index: usize,
+    ) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::TaskDetail(task_id.to_string()),
+            KeyCode::Enter => {
+                let text = self.todo_text_input.value().trim().to_string();
+                if text.is_empty() {
+                    return Ok(());
+                }
+
+                self.task_manager
+                    .update_todo_text(task_id, todo_index, text)?;
+                self.mode = AppMode::TaskDetail(task_id.to_string());
+            }
+            key_code => {
+                self.todo_text_input.handle_key(key_code);
+            }
+        }
+        Ok(())
+    }
+
+    /// Enter sets the typed due date, or removes it when the input is empty. Input
+    /// that isn't a date shows an error and stays to be corrected.
+    fn handle_edit_due_date_input(&mut self, key: KeyEvent, task_id: &str) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::TaskDetail(task_id.to_string()),
+            KeyCode::Enter => {
+                let input = self.due_date_input.value().trim();
+                let due_date = if input.is_empty() {
+                    None
+                } else {
+                    match parse_due_date(input, chrono::Local::now().date_naive()) {
+                        Ok(due_date) => Some(due_date),
+                        Err(e) => {
+                            self.push_notification(Severity::Error, e.to_string());
+                            return Ok(());
+                        }
+                    }
+                };
+
+                self.task_manager.set_due_date(task_id, due_date)?;
+                self.mode = AppMode::TaskDetail(task_id.to_string());
+            }
+            key_code => {
+                self.due_date_input.handle_key(key_code);
+            }
+        }
+        Ok(())
+    }
+
     fn handle_todo_state_select_input(
         &mut self,
-        key_code: KeyCode,
+        key: KeyEvent,
         task_id: &str,
         todo_index: usize,
     ) -> Result<()> {
-        match key_code {
-            KeyCode::Esc | KeyCode::Char('q') => {
+        use Action::*;
+
+        let action = self
+            .config
+            .keybindings
+            .action(&[Back, Up, Down, Confirm], key.code);
+
+        match action {
+            Some(Back) => {
                 // Cancel and return to task detail
                 self.mode = AppMode::TaskDetail(task_id.to_string());
             }
-            KeyCode::Up => {
+            Some(Up) => {
                 // Move up in state list
                 let current = self.todo_state_list_state.selected().unwrap_or(0);
                 let new_index = if current > 0 { current - 1 } else { 7 }; // 8 total states, wrap around
                 self.todo_state_list_state.select(Some(new_index));
             }
-            KeyCode::Down => {
+            Some(Down) => {
                 // Move down in state list
                 let current = self.todo_state_list_state.selected().unwrap_or(0);
                 let new_index = if current < 7 { current + 1 } else { 0 }; // 8 total states, wrap around
                 self.todo_state_list_state.select(Some(new_index));
             }
-            KeyCode::Enter | KeyCode::Char(' ') => {
+            Some(Confirm) => {
                 // Apply selected state
                 if let Some(selected_state_index) = self.todo_state_list_state.selected() {
-                    let states = Self::get_all_todo_states();
+                    let states = TodoState::ALL;
                     if let Some(new_state) = states.get(selected_state_index) {
-                        self.task_manager
-                            .set_todo_state(task_id, todo_index, new_state.clone())?;
+                        let blockers = self.task_manager.set_todo_state(
+                            task_id,
+                            todo_index,
+                            new_state.clone(),
+                        )?;
+                        if let Some(warning) = Self::blocker_warning(&blockers) {
+                            self.push_notification(Severity::Info, warning);
+                        }
                     }
                 }
                 // Return to task detail
@@ -597,16 +2735,123 @@ impl App {
         Ok(())
     }
 
-    fn get_all_todo_states() -> Vec<TodoState> {
-        vec![
-            TodoState::Undone,
-            TodoState::Pending,
-            TodoState::Done,
-            TodoState::Urgent,
-            TodoState::Uncertain,
-            TodoState::OnHold,
-            TodoState::Cancelled,
-            TodoState::Recurring,
-        ]
+    /// Warning for a state change that completed a task with unfinished blockers. The
+    /// change itself still goes through, as finishing early is sometimes intended.
+    fn blocker_warning(blockers: &[&Task]) -> Option<String> {
+        (!blockers.is_empty()).then(|| {
+            let titles: Vec<&str> = blockers.iter().map(|task| task.title.as_str()).collect();
+            format!(
+                "Warning: this task is completed but still depends on unfinished tasks: {}",
+                titles.join(", ")
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An app on an empty data directory, which is removed again when dropped
+    struct TestApp {
+        app: App,
+        dir: PathBuf,
+    }
+
+    impl TestApp {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("norgdo-test-{}", uuid::Uuid::new_v4()));
+            // A default session keeps the tests away from the user's state.toml
+            let app = App::new(
+                Some(dir.clone()),
+                Config::default(),
+                SessionState::default(),
+            )
+            .unwrap();
+            Self { app, dir }
+        }
+
+        /// Waits for the background loads to be swapped in
+        fn finish_loading(&mut self) {
+            while self.app.pending_load.is_some() || self.app.pending_bodies.is_some() {
+                std::thread::sleep(Duration::from_millis(5));
+                self.app.finish_loading().unwrap();
+            }
+        }
+    }
+
+    impl Drop for TestApp {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn wizard_todos_are_trimmed_and_blank_ones_ignored() {
+        let mut test = TestApp::new();
+        let app = &mut test.app;
+
+        app.wizard_data.current_todo.set_value("   ");
+        app.commit_wizard_todo();
+        assert!(app.wizard_data.todos.is_empty());
+
+        app.wizard_data.current_todo.set_value("  Buy milk \t");
+        app.commit_wizard_todo();
+        let texts: Vec<&str> = app
+            .wizard_data
+            .todos
+            .iter()
+            .map(|t| t.text.as_str())
+            .collect();
+        assert_eq!(texts, ["Buy milk"]);
+        assert!(app.wizard_data.current_todo.is_empty());
+    }
+
+    #[test]
+    fn a_finished_load_keeps_unsaved_changes() {
+        let mut test = TestApp::new();
+        test.finish_loading();
+        let app = &mut test.app;
+        app.task_manager.set_auto_save(false);
+        let task_id = app
+            .task_manager
+            .create_task("Errands".to_string())
+            .unwrap()
+            .id
+            .clone();
+        let due = chrono::NaiveDate::from_ymd_opt(2030, 1, 1);
+        app.task_manager.set_due_date(&task_id, due).unwrap();
+
+        // Refreshing is refused outright
+        app.reload_tasks().unwrap();
+        assert!(app.pending_load.is_none());
+
+        // A load that was already running doesn't replace the changes either
+        let selection = app.selection();
+        app.start_loading(LoadPurpose::Refresh(selection));
+        test.finish_loading();
+        assert!(test.app.task_manager.has_unsaved_changes());
+        assert_eq!(test.app.task_manager.get_tasks()[0].due_date, due);
+    }
+
+    #[test]
+    fn task_bodies_load_in_the_background() {
+        let mut test = TestApp::new();
+        test.finish_loading();
+        std::fs::write(
+            test.dir.join("trip.norg"),
+            "* Trip\n\nBook the ferry to the island\n\n- ( ) Pack\n",
+        )
+        .unwrap();
+        test.app.reload_tasks().unwrap();
+        test.finish_loading();
+        assert!(test.app.task_manager.search_tasks("ferry").is_empty());
+
+        // The summaries stay in place until the bodies are swapped in
+        test.app.load_task_bodies();
+        assert!(test.app.pending_bodies.is_some());
+        assert!(test.app.task_manager.get_tasks()[0].summary_only);
+        test.finish_loading();
+        assert_eq!(test.app.task_manager.search_tasks("ferry").len(), 1);
     }
 }
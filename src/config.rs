@@ -0,0 +1,546 @@
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use crossterm::event::KeyCode;
+use directories::ProjectDirs;
+use norgdo::task::{CompletionMode, CompletionRules, KanbanCategory, KanbanColumn, TodoState};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+const MIN_TICK_RATE_MS: u64 = 16;
+
+/// User configuration, read from `config.toml` in the platform config directory
+/// (e.g. `~/.config/norgdo/config.toml`). Every section is optional.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Write task files after every change instead of waiting for an explicit save
+    pub auto_save: bool,
+    /// How often, in milliseconds, the UI redraws and checks for file changes when
+    /// there is no input
+    pub tick_rate_ms: u64,
+    /// `strftime`-style format of the dashboard clock, empty to hide it
+    pub clock_format: String,
+    /// How many days ahead the agenda view looks
+    pub agenda_days: u32,
+    /// How todos add up to the completion shown on cards
+    pub completion_mode: CompletionMode,
+    /// Whether cancelled todos count as completed, or are left out of completion
+    pub count_cancelled: bool,
+    /// Moving past the last item of a list goes back to the first one, and vice versa
+    pub wrap_navigation: bool,
+    /// `gg` jumps to the first card or todo and `G` to the last one
+    pub vim_navigation: bool,
+    /// Extra kanban columns, shown between In Progress and Completed. The three built-in
+    /// columns are always there and can't be changed.
+    pub columns: Vec<KanbanColumn>,
+    pub keybindings: KeyBindings,
+    pub theme: Theme,
+    #[serde(skip)]
+    warnings: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            auto_save: true,
+            tick_rate_ms: 250,
+            clock_format: "%Y-%m-%d %H:%M".to_string(),
+            agenda_days: 7,
+            completion_mode: CompletionMode::default(),
+            count_cancelled: true,
+            wrap_navigation: false,
+            vim_navigation: false,
+            columns: Vec::new(),
+            keybindings: KeyBindings::default(),
+            theme: Theme::default(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config file, falling back to the defaults when there is none
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::config_path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Could not read config file {}", path.display()))?;
+        let mut config: Self = toml::from_str(&contents)
+            .wrap_err_with(|| format!("Invalid config file {}", path.display()))?;
+        config.resolve_vim_conflicts();
+        config.check_columns();
+        Ok(config)
+    }
+
+    /// Problems found while reading the config, to be shown once the UI is up
+    pub fn warnings(&self) -> impl Iterator<Item = &String> {
+        self.warnings.iter().chain(self.theme.warnings())
+    }
+
+    /// Drops extra columns whose name is already used by a built-in column or an
+    /// earlier extra one, as tasks couldn't tell them apart
+    fn check_columns(&mut self) {
+        let mut taken: Vec<String> = KanbanCategory::BUILT_IN
+            .iter()
+            .map(|category| category.to_string().to_lowercase())
+            .collect();
+        let warnings = &mut self.warnings;
+        self.columns.retain(|column| {
+            let name = column.name.trim().to_lowercase();
+            if taken.contains(&name) {
+                warnings.push(format!(
+                    "Column name '{}' is already taken, ignoring the column",
+                    column.name
+                ));
+                return false;
+            }
+            taken.push(name);
+            true
+        });
+    }
+
+    /// With vim navigation, the `vim_top` key starts a `gg` and never reaches other
+    /// actions. `toggle_agenda` shares `g` by default, so it moves to `D` then.
+    fn resolve_vim_conflicts(&mut self) {
+        if self.vim_navigation {
+            self.keybindings.move_shared_keys(
+                Action::ToggleAgenda,
+                Action::VimTop,
+                KeyCode::Char('D'),
+            );
+        }
+    }
+
+    pub fn completion_rules(&self) -> CompletionRules {
+        CompletionRules {
+            mode: self.completion_mode,
+            count_cancelled: self.count_cancelled,
+        }
+    }
+
+    /// How long the main loop waits for input before redrawing anyway
+    pub fn tick_rate(&self) -> Duration {
+        // Very short ticks would only burn CPU
+        Duration::from_millis(self.tick_rate_ms.max(MIN_TICK_RATE_MS))
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "norgdo").map(|dirs| dirs.config_dir().join("config.toml"))
+    }
+}
+
+/// Everything a key can be bound to outside of text inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    NewTask,
+    QuickAdd,
+    Search,
+    JumpToTask,
+    Filter,
+    ClearFilter,
+    CycleTagFilter,
+    Refresh,
+    Help,
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Open,
+    Back,
+    Confirm,
+    Save,
+    OpenEditor,
+    SelectState,
+    DeleteTodo,
+    ShowParseErrors,
+    Archive,
+    ArchiveCompleted,
+    ToggleBoardMode,
+    CompleteAll,
+    Duplicate,
+    NewFromTemplate,
+    ToggleAgenda,
+    ToggleAgendaOverdue,
+    EditTodo,
+    EditDueDate,
+    IndentTodo,
+    OutdentTodo,
+    AllTodos,
+    FocusYetToBeDone,
+    FocusInProgress,
+    FocusCompleted,
+    FocusExtraColumn, // The nth key focuses the nth extra column
+    NextSection,
+    PrevSection,
+    ToggleCompleted,
+    VimTop,    // Pressed twice, only with vim navigation
+    VimBottom, // Only with vim navigation
+}
+
+/// One key or a list of keys, so `up = "k"` and `up = ["Up", "k"]` both work
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Maps actions to the keys that trigger them. Actions listed in the config file
+/// replace their default keys, the rest keep them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "HashMap<Action, KeyList>")]
+pub struct KeyBindings {
+    bindings: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl KeyBindings {
+    /// The first of `actions` bound to `key_code`. Each view passes the actions it
+    /// supports, so the same key can mean different things in different views.
+    pub fn action(&self, actions: &[Action], key_code: KeyCode) -> Option<Action> {
+        actions.iter().copied().find(|action| {
+            self.bindings
+                .get(action)
+                .is_some_and(|keys| keys.contains(&key_code))
+        })
+    }
+
+    /// Position of `key_code` among the keys bound to `action`
+    pub fn key_index(&self, action: Action, key_code: KeyCode) -> Option<usize> {
+        self.bindings
+            .get(&action)?
+            .iter()
+            .position(|key| *key == key_code)
+    }
+
+    /// Name of the first key bound to `action`, for showing in hints
+    pub fn key_name(&self, action: Action) -> Option<String> {
+        let key_code = self.bindings.get(&action)?.first()?;
+        Some(key_name(*key_code))
+    }
+
+    /// Unbinds the keys `action` shares with `other`, binding `fallback` instead if
+    /// that leaves `action` without any
+    fn move_shared_keys(&mut self, action: Action, other: Action, fallback: KeyCode) {
+        let other_keys = self.bindings.get(&other).cloned().unwrap_or_default();
+        let keys = self.bindings.entry(action).or_default();
+        keys.retain(|key| !other_keys.contains(key));
+        if keys.is_empty() {
+            keys.push(fallback);
+        }
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use Action::*;
+
+        let bindings = [
+            (Quit, vec![KeyCode::Char('q')]),
+            (NewTask, vec![KeyCode::Char('n')]),
+            (QuickAdd, vec![KeyCode::Char('N')]),
+            (Search, vec![KeyCode::Char('/')]),
+            (JumpToTask, vec![KeyCode::Char(':'), KeyCode::Char('J')]),
+            (Filter, vec![KeyCode::Char('f')]),
+            (ClearFilter, vec![KeyCode::Esc]),
+            (CycleTagFilter, vec![KeyCode::Char('t')]),
+            (Refresh, vec![KeyCode::Char('r')]),
+            (Help, vec![KeyCode::Char('?')]),
+            (Left, vec![KeyCode::Left]),
+            (Right, vec![KeyCode::Right]),
+            (Up, vec![KeyCode::Up]),
+            (Down, vec![KeyCode::Down]),
+            (PageUp, vec![KeyCode::PageUp]),
+            (PageDown, vec![KeyCode::PageDown]),
+            (Top, vec![KeyCode::Home]),
+            (Bottom, vec![KeyCode::End]),
+            (Open, vec![KeyCode::Enter]),
+            (Back, vec![KeyCode::Esc]),
+            (Confirm, vec![KeyCode::Enter, KeyCode::Char(' ')]),
+            (Save, vec![KeyCode::Char('s')]),
+            (OpenEditor, vec![KeyCode::Char('e')]),
+            (SelectState, vec![KeyCode::Char(' ')]),
+            (DeleteTodo, vec![KeyCode::Delete]),
+            (ShowParseErrors, vec![KeyCode::Char('!')]),
+            (Archive, vec![KeyCode::Char('a')]),
+            (ArchiveCompleted, vec![KeyCode::Char('A')]),
+            (ToggleBoardMode, vec![KeyCode::Char('p')]),
+            (CompleteAll, vec![KeyCode::Char('A')]),
+            (Duplicate, vec![KeyCode::Char('c')]),
+            (NewFromTemplate, vec![KeyCode::Char('T')]),
+            (ToggleAgenda, vec![KeyCode::Char('g')]),
+            (ToggleAgendaOverdue, vec![KeyCode::Char('o')]),
+            (EditTodo, vec![KeyCode::F(2)]),
+            (EditDueDate, vec![KeyCode::Char('d')]),
+            (IndentTodo, vec![KeyCode::Tab]),
+            (OutdentTodo, vec![KeyCode::BackTab]),
+            (AllTodos, vec![KeyCode::Char('L')]),
+            (FocusYetToBeDone, vec![KeyCode::Char('1')]),
+            (FocusInProgress, vec![KeyCode::Char('2')]),
+            (FocusCompleted, vec![KeyCode::Char('3')]),
+            (FocusExtraColumn, ('4'..='9').map(KeyCode::Char).collect()),
+            (NextSection, vec![KeyCode::Tab]),
+            (PrevSection, vec![KeyCode::BackTab]),
+            (ToggleCompleted, vec![KeyCode::Char('C')]),
+            (VimTop, vec![KeyCode::Char('g')]),
+            (VimBottom, vec![KeyCode::Char('G')]),
+        ];
+
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+impl TryFrom<HashMap<Action, KeyList>> for KeyBindings {
+    type Error = String;
+
+    fn try_from(overrides: HashMap<Action, KeyList>) -> std::result::Result<Self, Self::Error> {
+        let mut keybindings = Self::default();
+        for (action, keys) in overrides {
+            let keys = match keys {
+                KeyList::One(key) => vec![key],
+                KeyList::Many(keys) => keys,
+            };
+            let keys = keys
+                .iter()
+                .map(|key| parse_key(key))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            keybindings.bindings.insert(action, keys);
+        }
+        Ok(keybindings)
+    }
+}
+
+/// Parses a key name such as `k`, `Space`, `Enter`, `PageDown` or `F2`
+fn parse_key(key: &str) -> std::result::Result<KeyCode, String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+
+    let key_code = match key.to_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        name => name
+            .strip_prefix('f')
+            .and_then(|number| number.parse::<u8>().ok())
+            .filter(|number| (1..=12).contains(number))
+            .map(KeyCode::F)
+            .ok_or_else(|| format!("unknown key '{}'", key))?,
+    };
+    Ok(key_code)
+}
+
+/// The name of a key as shown in hints, e.g. `k`, `Space` or `PageDown`
+fn key_name(key_code: KeyCode) -> String {
+    match key_code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(number) => format!("F{}", number),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        key_code => format!("{:?}", key_code),
+    }
+}
+
+/// Colors for each semantic role in the UI. Roles set in the config file override
+/// the defaults, and invalid entries keep the default with a warning.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "HashMap<String, toml::Value>")]
+pub struct Theme {
+    pub title: Color,
+    pub border: Color,
+    pub focused_border: Color,
+    pub text: Color,
+    pub muted: Color,
+    pub selection: Color, // Background of the selected row
+    pub error: Color,
+    pub undone: Color,
+    pub pending: Color,
+    pub done: Color,
+    pub urgent: Color,
+    pub uncertain: Color,
+    pub on_hold: Color,
+    pub cancelled: Color,
+    pub recurring: Color,
+    warnings: Vec<String>,
+}
+
+impl Theme {
+    pub fn todo_state(&self, state: &TodoState) -> Color {
+        match state {
+            TodoState::Undone => self.undone,
+            TodoState::Pending => self.pending,
+            TodoState::Done => self.done,
+            TodoState::Urgent => self.urgent,
+            TodoState::Uncertain => self.uncertain,
+            TodoState::OnHold => self.on_hold,
+            TodoState::Cancelled => self.cancelled,
+            TodoState::Recurring => self.recurring,
+        }
+    }
+
+    /// Problems found while reading the theme, to be shown once the UI is up
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        let color = match role {
+            "title" => &mut self.title,
+            "border" => &mut self.border,
+            "focused_border" => &mut self.focused_border,
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "selection" => &mut self.selection,
+            "error" => &mut self.error,
+            "undone" => &mut self.undone,
+            "pending" => &mut self.pending,
+            "done" => &mut self.done,
+            "urgent" => &mut self.urgent,
+            "uncertain" => &mut self.uncertain,
+            "on_hold" => &mut self.on_hold,
+            "cancelled" => &mut self.cancelled,
+            "recurring" => &mut self.recurring,
+            _ => return None,
+        };
+        Some(color)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            title: Color::Cyan,
+            border: Color::White,
+            focused_border: Color::Yellow,
+            text: Color::White,
+            muted: Color::Gray,
+            selection: Color::Black,
+            error: Color::Red,
+            undone: Color::White,
+            pending: Color::Blue,
+            done: Color::Green,
+            urgent: Color::Yellow,
+            uncertain: Color::Magenta,
+            on_hold: Color::Cyan,
+            cancelled: Color::Red,
+            recurring: Color::LightYellow,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl From<HashMap<String, toml::Value>> for Theme {
+    fn from(colors: HashMap<String, toml::Value>) -> Self {
+        let mut theme = Self::default();
+        let mut warnings = Vec::new();
+
+        for (role, value) in colors {
+            let Some(color) = theme.role_mut(&role) else {
+                warnings.push(format!("Unknown theme role '{}', ignoring it", role));
+                continue;
+            };
+            let Some(name) = value.as_str() else {
+                warnings.push(format!(
+                    "Theme role '{}' must be a color name, got {}, using the default",
+                    role, value
+                ));
+                continue;
+            };
+            match Color::from_str(name) {
+                Ok(parsed) => *color = parsed,
+                Err(_) => warnings.push(format!(
+                    "Unknown color '{}' for theme role '{}', using the default",
+                    name, role
+                )),
+            }
+        }
+
+        warnings.sort();
+        theme.warnings = warnings;
+        theme
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vim_navigation_moves_the_agenda_off_g() {
+        let mut config: Config = toml::from_str("vim_navigation = true\n").unwrap();
+        config.resolve_vim_conflicts();
+        let keybindings = &config.keybindings;
+
+        assert_eq!(
+            keybindings.key_name(Action::ToggleAgenda).as_deref(),
+            Some("D")
+        );
+        assert_eq!(
+            keybindings.action(&[Action::ToggleAgenda, Action::VimTop], KeyCode::Char('g')),
+            Some(Action::VimTop)
+        );
+
+        // Keys the user picked are kept as long as they don't clash
+        let mut config: Config =
+            toml::from_str("vim_navigation = true\n[keybindings]\nvim_top = \"z\"\n").unwrap();
+        config.resolve_vim_conflicts();
+        assert_eq!(
+            config.keybindings.key_name(Action::ToggleAgenda).as_deref(),
+            Some("g")
+        );
+    }
+
+    #[test]
+    fn columns_with_taken_names_are_dropped() {
+        let mut config: Config = toml::from_str(
+            "[[columns]]\nname = \"completed\"\nstates = [\"on_hold\"]\n\n[[columns]]\nname = \"Blocked\"\nstates = [\"on_hold\"]\n\n[[columns]]\nname = \"Blocked\"\nstates = [\"urgent\"]\n",
+        )
+        .unwrap();
+        config.check_columns();
+
+        let names: Vec<&str> = config.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Blocked"]);
+        assert_eq!(config.warnings().count(), 2);
+    }
+
+    #[test]
+    fn non_string_theme_values_become_warnings() {
+        let config: Config = toml::from_str("[theme]\ntext = 3\ntitle = \"red\"\n").unwrap();
+
+        assert_eq!(config.theme.text, Theme::default().text);
+        assert_eq!(config.theme.title, Color::Red);
+        assert_eq!(config.theme.warnings().len(), 1);
+        assert!(config.theme.warnings()[0].contains("'text'"));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::task::{Task, TodoState};
+
+/// Renders a task as GitHub-flavored Markdown, e.g. for pasting into an issue.
+/// Todos become checkboxes, indented two spaces per nesting level below the top.
+/// Only done todos are checked. Cancelled ones stay unchecked and are struck
+/// through, and states other than done or undone are annotated after the text.
+pub fn to_markdown(task: &Task) -> String {
+    let mut markdown = format!("# {}\n", task.title);
+
+    if !task.description.is_empty() {
+        markdown.push_str(&format!("\n{}\n", task.description));
+    }
+
+    if !task.todos.is_empty() {
+        markdown.push('\n');
+        for todo in &task.todos {
+            let indent = "  ".repeat(todo.level.saturating_sub(1));
+            let checkbox = if todo.state == TodoState::Done {
+                "x"
+            } else {
+                " "
+            };
+            let text = match todo.state {
+                TodoState::Undone | TodoState::Done => todo.text.clone(),
+                TodoState::Cancelled => format!("~~{}~~ *(Cancelled)*", todo.text),
+                ref state => format!("{} *({})*", todo.text, state.to_string()),
+            };
+            let marker = if todo.ordered { "1." } else { "-" };
+            markdown.push_str(&format!("{}{} [{}] {}\n", indent, marker, checkbox, text));
+        }
+    }
+
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::TodoItem;
+    use std::path::PathBuf;
+
+    #[test]
+    fn cancelled_todos_are_struck_through_but_unchecked() {
+        let mut task = Task::new("Chores".to_string(), PathBuf::from("chores.norg"));
+        task.todos = vec![
+            TodoItem::new("Dishes".to_string(), TodoState::Done, 1),
+            TodoItem::new("Laundry".to_string(), TodoState::Cancelled, 1),
+        ];
+
+        let markdown = to_markdown(&task);
+
+        assert!(markdown.contains("- [x] Dishes\n"));
+        assert!(markdown.contains("- [ ] ~~Laundry~~ *(Cancelled)*\n"));
+    }
+}
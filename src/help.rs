@@ -0,0 +1,180 @@
+//! The help popup's content, split into sections so it can be paged a section at a time
+
+/// A labeled group of help lines, such as the keys of one view
+pub struct HelpSection {
+    pub title: &'static str,
+    pub lines: &'static [&'static str],
+}
+
+/// Left out of the help when auto-save is on, as there is nothing to save by hand
+pub const SAVE_HELP_LINE: &str = "  s                   Save changes to file";
+
+pub const HELP_SECTIONS: &[HelpSection] = &[
+    HelpSection {
+        title: "DASHBOARD NAVIGATION",
+        lines: &[
+            "  Left/Right (← →)    Switch between kanban columns",
+            "  1/2/3               Jump to Yet to be Done/In Progress/Completed",
+            "  4-9                 Jump to the extra columns from the config",
+            "  Up/Down (↑ ↓)       Navigate within a column",
+            "  Home/End            Jump to the first/last card (also gg/G",
+            "                      with vim_navigation)",
+            "  Enter               Open selected task details",
+            "  Mouse click         Select a card, click again to open it",
+            "  Mouse wheel         Move the selection",
+        ],
+    },
+    HelpSection {
+        title: "TASK MANAGEMENT",
+        lines: &[
+            "  n                   Create new task",
+            "  N                   Quick-add a task with just a title",
+            "  r                   Refresh tasks from disk",
+            "  t                   Cycle tag filter",
+            "  a                   Archive selected completed task",
+            "  A                   Archive all completed tasks",
+            "  p                   Toggle grouping by project",
+            "  C                   Show or hide the Completed column",
+            "  c                   Duplicate selected task",
+            "  T                   Create a task from a template",
+            "  g                   Toggle agenda (tasks due soon, by due date),",
+            "                      D with vim_navigation",
+            "  o                   Show or hide overdue tasks in the agenda",
+            "  L                   List all TODOs across tasks",
+            "  f                   Filter tasks (is:urgent tag:work text)",
+            "  Esc                 Clear active filters",
+            "  /                   Search tasks",
+            "  : or J              Jump to a task by typing part of it",
+            "  !                   Show files that failed to parse",
+        ],
+    },
+    HelpSection {
+        title: "SEARCH",
+        lines: &[
+            "  Type                Filter tasks as you type",
+            "  Up/Down (↑ ↓)       Navigate results",
+            "  Enter               Open highlighted task",
+            "  Esc                 Cancel search",
+        ],
+    },
+    HelpSection {
+        title: "TASK CREATION WIZARD",
+        lines: &[
+            "  Enter               Continue to next step / Add TODO item",
+            "  Alt+Enter           New line in the description (step 2)",
+            "  Tab/Shift+Tab       Indent/outdent TODO item (step 4)",
+            "  Ctrl+S              Cycle initial TODO state (step 4)",
+            "  Left/Right (← →)    Move the cursor within the input",
+            "  Home/End            Jump to start/end of the current line",
+            "  Left (←)            Go back to previous step (cursor at start)",
+            "  Up/Down (↑ ↓)       Navigate TODO list (step 4)",
+            "  Backspace           Delete character / Go back (empty input)",
+            "  Delete              Delete character after the cursor /",
+            "                      Remove selected TODO item (step 4)",
+            "  F2                  Edit selected TODO item (step 4)",
+            "  Y/N                 Confirm/Cancel task creation (final step)",
+            "  Esc                 Cancel wizard and return to dashboard,",
+            "                      asking first if anything was entered",
+        ],
+    },
+    HelpSection {
+        title: "TASK DETAIL VIEW",
+        lines: &[
+            "  Up/Down (↑ ↓)       Navigate between TODO items",
+            "  Home/End            Jump to the first/last TODO (also gg/G)",
+            "  PageUp/PageDown     Scroll a long description",
+            "  Ctrl+D/Ctrl+U       Move half a page down/up the TODO list",
+            "  Space               Open TODO state selection dialog",
+            "  1-8                 Set state directly (in dialog order)",
+            "  x - = _ + !         Set state by its Norg character",
+            "  Delete              Remove TODO item (and its sub-items)",
+            "  A                   Mark all TODO items done (again to undo)",
+            "  F2                  Edit TODO text (Enter: save, Esc: cancel)",
+            "  d                   Set due date (e.g. tomorrow, +3d, next fri)",
+            "  Tab/Shift+Tab       Indent/outdent TODO with its sub-items",
+            SAVE_HELP_LINE,
+            "  e                   Open task in $EDITOR",
+            "  : or J              Jump to another task",
+            "  Esc                 Return to dashboard",
+        ],
+    },
+    HelpSection {
+        title: "TODO STATE SELECTION",
+        lines: &[
+            "  Up/Down (↑ ↓)       Navigate state options",
+            "  Enter/Space         Select state and apply",
+            "  Esc                 Cancel state selection",
+        ],
+    },
+    HelpSection {
+        title: "TODO STATES",
+        lines: &[
+            "   Undone            Task not started",
+            "   Pending           Task in progress",
+            "   Done              Task completed",
+            "   Urgent            High priority task",
+            "   Uncertain         Task status unclear",
+            "   On Hold           Task paused",
+            "   Cancelled         Task cancelled",
+            "   Recurring         Recurring task",
+        ],
+    },
+    HelpSection {
+        title: "KANBAN COLUMNS",
+        lines: &[
+            "  Yet to be Done      Nothing started: only undone todos, or none",
+            "  In Progress         A pending, urgent, on-hold or uncertain",
+            "                      todo, or some todos completed (recurring",
+            "                      todos alone don't count)",
+            "  Completed           All todos done or cancelled",
+            "  Extra columns       From the config, see the README",
+        ],
+    },
+    HelpSection {
+        title: "GENERAL",
+        lines: &[
+            "  ?                   Show/hide this help",
+            "  Enter/Esc           Dismiss the newest error message",
+            "  Esc                 Back out one level: close a popup or dialog,",
+            "                      leave a view, or cancel an input",
+            "  q                   Quit application (from the dashboard only,",
+            "                      elsewhere it does nothing or types a q)",
+        ],
+    },
+    HelpSection {
+        title: "HELP NAVIGATION",
+        lines: &[
+            "  Up/Down (↑ ↓)       Scroll help content",
+            "  Page Up/Down        Jump to the previous/next section",
+            "  Ctrl+D/Ctrl+U       Scroll half a page down/up",
+            "  Tab/Shift+Tab       Cycle through sections",
+            "  Home                Go to top",
+            "  End                 Go to bottom",
+        ],
+    },
+];
+
+const HELP_FOOTER: &str = "Press ? or Esc to close this help";
+
+/// The help text as displayed, with a blank line after each section, along with
+/// the line each section's title is on
+pub fn help_content(auto_save: bool) -> (Vec<String>, Vec<usize>) {
+    let mut lines = Vec::new();
+    let mut section_starts = Vec::with_capacity(HELP_SECTIONS.len());
+
+    for section in HELP_SECTIONS {
+        section_starts.push(lines.len());
+        lines.push(format!("{}:", section.title));
+        lines.extend(
+            section
+                .lines
+                .iter()
+                .filter(|line| !(auto_save && **line == SAVE_HELP_LINE))
+                .map(|line| line.to_string()),
+        );
+        lines.push(String::new());
+    }
+    lines.push(HELP_FOOTER.to_string());
+
+    (lines, section_starts)
+}
@@ -0,0 +1,136 @@
+use crossterm::event::KeyCode;
+use std::fmt;
+
+/// A text input with a cursor, shared by the wizard, search and filter fields. Only the
+/// wizard description holds more than one line
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextInput {
+    value: String,
+    cursor: usize, // Byte offset into `value`, always on a char boundary
+}
+
+impl TextInput {
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn is_at_start(&self) -> bool {
+        self.cursor == 0
+    }
+
+    /// The text before the cursor, used to place the terminal cursor
+    pub fn before_cursor(&self) -> &str {
+        &self.value[..self.cursor]
+    }
+
+    /// Replaces the content, leaving the cursor at the end
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Inserts text at the cursor, e.g. when pasting
+    pub fn insert_str(&mut self, text: &str) {
+        self.value.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(c) = self.value[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.value.remove(self.cursor);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.value.len() {
+            self.value.remove(self.cursor);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if let Some(c) = self.value[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if let Some(c) = self.value[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    /// Moves to the start of the line the cursor is on
+    pub fn move_home(&mut self) {
+        self.cursor = self.value[..self.cursor]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+    }
+
+    /// Moves to the end of the line the cursor is on
+    pub fn move_end(&mut self) {
+        self.cursor += self.value[self.cursor..]
+            .find('\n')
+            .unwrap_or(self.value.len() - self.cursor);
+    }
+
+    /// Applies a text editing key, returning whether it was handled
+    pub fn handle_key(&mut self, key_code: KeyCode) -> bool {
+        match key_code {
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.move_home(),
+            KeyCode::End => self.move_end(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl fmt::Display for TextInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn home_and_end_stay_on_the_current_line() {
+        let mut input = TextInput::default();
+        input.set_value("first\nsecond\nthird");
+        input.move_home();
+        assert_eq!(input.before_cursor(), "first\nsecond\n");
+        input.move_left();
+        input.move_left();
+        input.move_end();
+        assert_eq!(input.before_cursor(), "first\nsecond");
+        input.move_end();
+        assert_eq!(input.before_cursor(), "first\nsecond");
+
+        input.set_value("one line");
+        input.move_home();
+        assert!(input.is_at_start());
+        input.move_end();
+        assert_eq!(input.before_cursor(), "one line");
+    }
+}
@@ -0,0 +1,36 @@
+//! Norgdo's task model, Norg parsing and task storage, usable without the TUI.
+//!
+//! ```no_run
+//! use norgdo::{TaskFilter, TaskManager};
+//!
+//! fn main() -> color_eyre::Result<()> {
+//!     // `None` uses the same data directory as the TUI
+//!     let manager = TaskManager::new(None)?;
+//!     for task in manager.get_tasks() {
+//!         println!("{}: {:.0}%", task.title, task.completion_percentage());
+//!     }
+//!
+//!     let urgent = manager.search_tasks("is:urgent");
+//!     println!("{} tasks with urgent todos", urgent.len());
+//!
+//!     let by_category = manager.get_tasks_by_category(&TaskFilter::default());
+//!     println!("{} categories in use", by_category.len());
+//!     Ok(())
+//! }
+//! ```
+
+pub mod export;
+pub mod parser;
+pub mod task;
+pub mod task_manager;
+
+#[cfg(test)]
+mod test_support;
+
+pub use export::to_markdown;
+pub use parser::NorgParser;
+pub use task::{
+    CompletionMode, CompletionRules, KanbanCategory, KanbanColumn, Task, TodoItem, TodoState,
+    parse_due_date,
+};
+pub use task_manager::{Stats, TaskFilter, TaskManager};
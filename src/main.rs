@@ -1,35 +1,244 @@
 mod app;
-mod parser;
-mod task;
-mod task_manager;
+mod config;
+mod help;
+mod input;
+mod session;
 mod ui;
+mod watcher;
 
 use app::App;
 use color_eyre::Result;
+use config::Config;
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+};
+use crossterm::execute;
+use norgdo::{Task, TaskManager};
 use ratatui::{DefaultTerminal, Frame};
+use session::SessionState;
+use std::io::stdout;
+use std::path::PathBuf;
+
+const USAGE: &str =
+    "Usage: norgdo [--dir <path>] [export [--format json|md] [--task <id>] | stats]";
+
+/// Command line arguments
+struct Args {
+    data_dir: Option<PathBuf>,
+    command: Command,
+}
+
+/// What to run, the TUI unless a subcommand is given
+enum Command {
+    Tui,
+    /// Print every task, or just one, to stdout without launching the TUI
+    Export {
+        format: ExportFormat,
+        task_id: Option<String>,
+    },
+    /// Print totals across all tasks, e.g. for a weekly review
+    Stats,
+}
+
+enum ExportFormat {
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    fn parse(format: &str) -> Result<Self> {
+        match format {
+            "json" => Ok(Self::Json),
+            "md" | "markdown" => Ok(Self::Markdown),
+            _ => Err(color_eyre::eyre::eyre!(
+                "Unknown export format '{}'. Supported formats: json, md",
+                format
+            )),
+        }
+    }
+}
+
+impl Args {
+    fn parse() -> Result<Self> {
+        let mut data_dir = None;
+        let mut command = Command::Tui;
+        let mut args = std::env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            if arg == "--dir" {
+                let dir = args
+                    .next()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("--dir requires a path argument"))?;
+                data_dir = Some(expand_home(&dir));
+            } else if let Some(dir) = arg.strip_prefix("--dir=") {
+                data_dir = Some(expand_home(dir));
+            } else if arg == "export" && matches!(command, Command::Tui) {
+                command = Command::Export {
+                    format: ExportFormat::Json,
+                    task_id: None,
+                };
+            } else if arg == "stats" && matches!(command, Command::Tui) {
+                command = Command::Stats;
+            } else if let Command::Export { format, .. } = &mut command
+                && (arg == "--format" || arg.starts_with("--format="))
+            {
+                let value = match arg.strip_prefix("--format=") {
+                    Some(value) => value.to_string(),
+                    None => args.next().ok_or_else(|| {
+                        color_eyre::eyre::eyre!("--format requires a format argument")
+                    })?,
+                };
+                *format = ExportFormat::parse(&value)?;
+            } else if let Command::Export { task_id, .. } = &mut command
+                && (arg == "--task" || arg.starts_with("--task="))
+            {
+                let value = match arg.strip_prefix("--task=") {
+                    Some(value) => value.to_string(),
+                    None => args.next().ok_or_else(|| {
+                        color_eyre::eyre::eyre!("--task requires a task id argument")
+                    })?,
+                };
+                *task_id = Some(value);
+            } else {
+                return Err(color_eyre::eyre::eyre!(
+                    "Unknown argument '{}'. {}",
+                    arg,
+                    USAGE
+                ));
+            }
+        }
+
+        Ok(Self { data_dir, command })
+    }
+}
+
+/// Expands a leading `~` since the shell won't do it for `--dir=~/...`
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
 
 fn render(app: &mut App, frame: &mut Frame) {
     ui::render(app, frame);
 }
 
-fn run(mut terminal: DefaultTerminal) -> Result<()> {
-    let mut app = App::new()?;
+/// Sets up the terminal like `ratatui::init`, with mouse capture and bracketed paste
+/// (pasted text arrives at once instead of as key presses) enabled on top
+fn init_terminal() -> Result<DefaultTerminal> {
+    let terminal = ratatui::init();
+    if let Err(e) = execute!(stdout(), EnableMouseCapture, EnableBracketedPaste) {
+        ratatui::restore();
+        return Err(e.into());
+    }
+    Ok(terminal)
+}
+
+fn restore_terminal() {
+    // Restoring is best effort, we are on our way out or handing over to the editor anyway
+    let _ = execute!(stdout(), DisableBracketedPaste, DisableMouseCapture);
+    ratatui::restore();
+}
+
+fn run(mut terminal: DefaultTerminal, args: Args) -> Result<()> {
+    let mut app = App::new(args.data_dir, Config::load()?, SessionState::load())?;
 
     loop {
+        app.finish_loading()?;
+        app.expire_notifications();
         terminal.draw(|frame| render(&mut app, frame))?;
 
         app.handle_events()?;
+        app.reload_on_external_changes()?;
+
+        if let Some(request) = app.editor_request.take() {
+            // Hand the terminal over to the editor, then take it back
+            restore_terminal();
+            let result = app.open_in_editor(request);
+            terminal = init_terminal()?;
+            terminal.clear()?;
+            result?;
+        }
 
         if app.should_quit {
+            // Before the first load is done, the last run's view hasn't even been restored
+            // Losing the remembered view is not worth keeping the user from quitting
+            if !app.is_starting_up() {
+                let _ = app.session_state().save();
+            }
             break Ok(());
         }
     }
 }
 
+/// Prints all tasks, or the one matching `task_id`, to stdout. This only reads
+/// task files, so the data directory is not even created if it is missing.
+fn export(data_dir: Option<PathBuf>, format: ExportFormat, task_id: Option<String>) -> Result<()> {
+    let task_manager = TaskManager::open(data_dir)?;
+
+    let output = match (format, task_id) {
+        (ExportFormat::Json, None) => task_manager.export_json()?,
+        (ExportFormat::Json, Some(id)) => {
+            serde_json::to_string_pretty(find_task(&task_manager, &id)?)?
+        }
+        (ExportFormat::Markdown, None) => task_manager
+            .get_tasks()
+            .iter()
+            .map(norgdo::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        (ExportFormat::Markdown, Some(id)) => norgdo::to_markdown(find_task(&task_manager, &id)?),
+    };
+    print!("{}", output);
+    if !output.ends_with('\n') {
+        println!();
+    }
+    Ok(())
+}
+
+/// Prints task and todo totals to stdout. Like the board, this follows the completion
+/// rules and extra columns of the config.
+fn stats(data_dir: Option<PathBuf>) -> Result<()> {
+    let config = Config::load()?;
+    let mut task_manager = TaskManager::open(data_dir)?;
+    task_manager.set_completion_rules(config.completion_rules());
+    task_manager.set_columns(config.columns);
+
+    print!("{}", task_manager.stats());
+    Ok(())
+}
+
+/// Looks a task up by its id. A unique prefix is enough, as ids are long UUIDs.
+fn find_task<'a>(task_manager: &'a TaskManager, id: &str) -> Result<&'a Task> {
+    let mut matches = task_manager
+        .get_tasks()
+        .iter()
+        .filter(|task| task.id.starts_with(id));
+    match (matches.next(), matches.next()) {
+        (Some(task), None) => Ok(task),
+        (Some(_), Some(_)) => Err(color_eyre::eyre::eyre!(
+            "Task id '{}' is ambiguous, give more characters",
+            id
+        )),
+        (None, _) => Err(color_eyre::eyre::eyre!("No task with id '{}'", id)),
+    }
+}
+
 fn main() -> Result<()> {
     color_eyre::install()?;
-    let terminal = ratatui::init();
-    let result = run(terminal);
-    ratatui::restore();
+    let args = Args::parse()?;
+
+    match args.command {
+        Command::Export { format, task_id } => return export(args.data_dir, format, task_id),
+        Command::Stats => return stats(args.data_dir),
+        Command::Tui => {}
+    }
+
+    let terminal = init_terminal()?;
+    let result = run(terminal, args);
+    restore_terminal();
     result
 }
@@ -1,8 +1,9 @@
-use crate::task::{Task, TodoItem, TodoState};
+use crate::task::{RelationType, Task, TaskRelation, TodoItem, TodoState};
+use chrono::{DateTime, SecondsFormat, Utc};
 use color_eyre::Result;
 use rust_norg::{
-    DetachedModifierExtension, NestableDetachedModifier, NorgAST, NorgASTFlat, ParagraphSegment,
-    ParagraphSegmentToken, TodoStatus, parse_tree,
+    DetachedModifierExtension, LinkTarget, NestableDetachedModifier, NorgAST, NorgASTFlat,
+    ParagraphSegment, ParagraphSegmentToken, TodoStatus, parse_tree,
 };
 use std::fs;
 use std::path::Path;
@@ -11,67 +12,523 @@ pub struct NorgParser;
 
 impl NorgParser {
     pub fn parse_task_file(file_path: &Path) -> Result<Task> {
-        let content = fs::read_to_string(file_path)?;
+        let content = &Self::read_task_file(file_path)?;
 
         // Parse file
-        let ast = parse_tree(&content)
+        let ast = parse_tree(content)
             .map_err(|e| color_eyre::eyre::eyre!("Failed to parse Norg file: {:?}", e))?;
 
         // Extract title from the first heading
-        let title = Self::extract_title_from_ast(&ast);
+        let (title, heading_level) = Self::extract_title_from_ast(&ast);
         let mut task = Task::new(title, file_path.to_path_buf());
+        task.id = Task::id_from_path(file_path);
+        task.heading_level = heading_level;
+
+        // Restore task-level fields from the metadata block, if any
+        Self::apply_metadata_from_ast(&ast, &mut task);
+        task.relations = Self::extract_relations_from_ast(&ast);
 
         // Extract description and todos from AST
-        let (description, todos) = Self::extract_content_from_ast(&ast)?;
+        let (description, mut todos) = Self::extract_content_from_ast(&ast, false)?;
+        Self::assign_line_numbers(content, &mut todos);
+
+        // Collect tags from hashtags in the body, on top of any from the metadata
+        let body_tags = Self::extract_carryover_tags(&ast)
+            .into_iter()
+            .chain(Self::extract_tags_from_text(&description));
+        for tag in body_tags {
+            if !task.tags.contains(&tag) {
+                task.tags.push(tag);
+            }
+        }
+
         task.description = description;
         task.todos = todos;
+        task.crlf = Self::uses_crlf(content);
+
+        Ok(task)
+    }
+
+    /// Cheap pass for the board, reading only what the cards need: the title, metadata,
+    /// relations, tags and todo states. The description is left empty and todo texts
+    /// keep their markup as written, so the task has to go through `parse_task_file`
+    /// before it is shown in full or written back.
+    pub fn parse_task_summary(file_path: &Path) -> Result<Task> {
+        let content = Self::read_task_file(file_path)?;
+        let mut task = Task::new("Untitled Task".to_string(), file_path.to_path_buf());
+        task.id = Task::id_from_path(file_path);
+        task.crlf = Self::uses_crlf(&content);
+        task.summary_only = true;
+
+        let mut found_heading = false;
+        let mut ranged_tag: Option<(&str, String)> = None; // Name and content so far
+        let mut body_tags = Vec::new();
+        let last_end_tag = Self::last_end_tag(&content);
+
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+
+            if let Some((name, tag_content)) = &mut ranged_tag {
+                if Self::is_end_tag(line) {
+                    match *name {
+                        "document.meta" => Self::apply_metadata(tag_content, &mut task),
+                        "relations" => task.relations.extend(Self::parse_relations(tag_content)),
+                        _ => {}
+                    }
+                    ranged_tag = None;
+                } else {
+                    tag_content.push_str(line);
+                    tag_content.push('\n');
+                }
+                continue;
+            }
+
+            if Self::is_end_tag(line) {
+                // A stray end tag, ignored like in `normalize_todo_statuses`
+            } else if let Some(name) = Self::ranged_tag_name(line)
+                && last_end_tag.is_some_and(|end| end > index)
+            {
+                ranged_tag = Some((name, String::new()));
+            } else if let Some(tag) = trimmed.strip_prefix('#') {
+                let mut words = tag.split_whitespace();
+                body_tags.extend(words.next().map(str::to_string));
+                body_tags.extend(
+                    words
+                        .filter_map(|word| word.strip_prefix('#'))
+                        .filter(|tag| !tag.is_empty())
+                        .map(str::to_string),
+                );
+            } else if let Some(level) = Self::heading_level(trimmed)
+                && !found_heading
+            {
+                task.title = trimmed[level..].trim().to_string();
+                task.heading_level = level;
+                found_heading = true;
+            } else if let Some(mut todo) = Self::todo_from_line(line) {
+                todo.id = format!("todo_{}", task.todos.len() + 1);
+                todo.line_number = index + 1;
+                task.todos.push(todo);
+            } else if found_heading {
+                // Part of the description, which the full parse collects tags from
+                body_tags.extend(Self::extract_tags_from_text(line));
+            }
+        }
+
+        for tag in body_tags {
+            if !task.tags.contains(&tag) {
+                task.tags.push(tag);
+            }
+        }
 
         Ok(task)
     }
 
-    fn extract_title_from_ast(ast: &[NorgAST]) -> String {
+    /// Reads a task file as UTF-8 with its todo statuses normalized
+    fn read_task_file(file_path: &Path) -> Result<String> {
+        let bytes = fs::read(file_path)?;
+        let content = String::from_utf8(bytes).map_err(|e| {
+            color_eyre::eyre::eyre!(
+                "File is not valid UTF-8 (invalid byte at offset {}), re-save it as UTF-8",
+                e.utf8_error().valid_up_to()
+            )
+        })?;
+        // Editors on Windows often start files with a byte order mark
+        let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+        Ok(Self::normalize_todo_statuses(content))
+    }
+
+    /// Number of `*`s of a heading line, if it is one
+    fn heading_level(line: &str) -> Option<usize> {
+        let level = line.len() - line.trim_start_matches('*').len();
+        (level > 0 && line[level..].starts_with(char::is_whitespace)).then_some(level)
+    }
+
+    /// The todo of a list item line such as `-- (x) Write tests`, without its id and
+    /// line number. Only expects the statuses left by `normalize_todo_statuses`.
+    fn todo_from_line(line: &str) -> Option<TodoItem> {
+        let (start, end) = Self::todo_status_span(line)?;
+        let status = &line[start + 1..end - 1];
+        let (state, recurrence) = match status.strip_prefix('+') {
+            Some(date) => {
+                let date = Some(date.trim()).filter(|date| !date.is_empty());
+                (TodoState::Recurring, date.map(str::to_string))
+            }
+            None => match status.trim().chars().collect::<Vec<_>>()[..] {
+                [] => (TodoState::Undone, None),
+                [c] => (TodoState::from_norg_char(c)?, None),
+                _ => return None,
+            },
+        };
+
+        let marker = line.trim_start();
+        let level = marker.len() - marker.trim_start_matches(['-', '~']).len();
+        Some(TodoItem {
+            id: String::new(),
+            text: line[end..].trim().to_string(),
+            state,
+            level,
+            line_number: 0,
+            ordered: marker.starts_with('~'),
+            recurrence,
+            notes: Vec::new(),
+        })
+    }
+
+    /// Rewrites hand-typed todo statuses such as `( X )` or `(X)` to the `(x)` form
+    /// the Norg parser expects, leaving statuses with data like `(+ 2024-06-01)`
+    /// and the contents of verbatim blocks alone
+    fn normalize_todo_statuses(content: &str) -> String {
+        let mut normalized = String::with_capacity(content.len());
+        let mut in_verbatim = false;
+        let last_end_tag = Self::last_end_tag(content);
+
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            if Self::is_end_tag(line) {
+                in_verbatim = false;
+            } else if Self::ranged_tag_name(line).is_some()
+                && last_end_tag.is_some_and(|end| end > index)
+            {
+                in_verbatim = true;
+            }
+
+            match Self::todo_status_span(line).filter(|_| !in_verbatim) {
+                Some((start, end)) => {
+                    let status = line[start + 1..end - 1].trim();
+                    let state = match status.chars().collect::<Vec<_>>()[..] {
+                        [] => Some(TodoState::Undone),
+                        [c] => TodoState::from_norg_char(c.to_ascii_lowercase()),
+                        _ => None,
+                    };
+                    match state {
+                        Some(state) => {
+                            normalized.push_str(&line[..start]);
+                            normalized.push_str(&format!("({})", state.to_norg_char()));
+                            normalized.push_str(&line[end..]);
+                        }
+                        None => normalized.push_str(line),
+                    }
+                }
+                None => normalized.push_str(line),
+            }
+        }
+
+        normalized
+    }
+
+    /// The name of the ranged tag a line such as `@document.meta` or `@code lua` opens.
+    /// Callers also check for an `@end` further down, so a paragraph that happens to
+    /// start with `@someone` is still read as text.
+    fn ranged_tag_name(line: &str) -> Option<&str> {
+        let name = line
+            .trim()
+            .strip_prefix('@')?
+            .split(char::is_whitespace)
+            .next()?;
+        let is_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        (is_name && name != "end").then_some(name)
+    }
+
+    fn is_end_tag(line: &str) -> bool {
+        line.trim() == "@end"
+    }
+
+    /// Index of the last `@end` line, which any ranged tag has to open before
+    fn last_end_tag(content: &str) -> Option<usize> {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| Self::is_end_tag(line))
+            .map(|(index, _)| index)
+            .last()
+    }
+
+    /// Byte range of the `(...)` status of a list item line, including the parentheses
+    fn todo_status_span(line: &str) -> Option<(usize, usize)> {
+        let trimmed = line.trim_start();
+        let rest = trimmed.trim_start_matches(['-', '~']);
+        if rest.len() == trimmed.len() || !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        let rest = rest.trim_start();
+        let start = line.len() - rest.len();
+        rest.strip_prefix('(')?;
+        let end = start + rest.find(')')? + 1;
+        Some((start, end))
+    }
+
+    /// Whether most line breaks in `content` are CRLF rather than LF
+    fn uses_crlf(content: &str) -> bool {
+        let line_breaks = content.matches('\n').count();
+        let crlf_breaks = content.matches("\r\n").count();
+        crlf_breaks * 2 > line_breaks
+    }
+
+    /// The text and level of the first heading
+    fn extract_title_from_ast(ast: &[NorgAST]) -> (String, usize) {
         for node in ast {
-            if let NorgAST::Heading { title, .. } = node {
-                return Self::paragraph_to_string(title);
+            if let NorgAST::Heading { level, title, .. } = node {
+                return (Self::paragraph_to_norg(title), *level as usize);
+            }
+        }
+        ("Untitled Task".to_string(), 1)
+    }
+
+    /// Reads `id`, `created`, `updated` and `due` from a `@document.meta` block
+    fn apply_metadata_from_ast(ast: &[NorgAST], task: &mut Task) {
+        for node in ast {
+            if let NorgAST::VerbatimRangedTag { name, content, .. } = node
+                && name.iter().map(String::as_str).eq(["document", "meta"])
+            {
+                Self::apply_metadata(content, task);
+            }
+        }
+    }
+
+    /// Applies the `key: value` entries of a `@document.meta` block to the task. Entries
+    /// norgdo doesn't use, and a `due` it can't read, are kept in `extra_metadata`.
+    /// The ones it does use are written anew on save, so empty or invalid values of
+    /// those are dropped.
+    fn apply_metadata(content: &str, task: &mut Task) {
+        for entry in Self::metadata_entries(content) {
+            let (key, value) = entry.split_once(':').unwrap_or((&entry, ""));
+            let value = value.trim();
+
+            match key.trim() {
+                "id" => {
+                    if !value.is_empty() {
+                        task.id = value.to_string();
+                    }
+                }
+                "created" => {
+                    if let Some(created_at) = Self::parse_timestamp(value) {
+                        task.created_at = created_at;
+                    }
+                }
+                "updated" => {
+                    if let Some(updated_at) = Self::parse_timestamp(value) {
+                        task.updated_at = updated_at;
+                    }
+                }
+                "tags" => {
+                    for tag in value
+                        .trim_matches(|c| c == '[' || c == ']')
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .map(|tag| tag.trim_start_matches('#'))
+                        .filter(|tag| !tag.is_empty())
+                    {
+                        if !task.tags.iter().any(|t| t == tag) {
+                            task.tags.push(tag.to_string());
+                        }
+                    }
+                }
+                "due" if value.is_empty() => {}
+                "due" => match chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                    Ok(due_date) => task.due_date = Some(due_date),
+                    Err(_) => task.extra_metadata.push(entry),
+                },
+                _ => task.extra_metadata.push(entry),
+            }
+        }
+    }
+
+    /// Splits the content of a `@document.meta` block into its entries. An entry starts
+    /// with an unindented `key:` line and takes the indented or closing lines after it
+    /// along, as in a multi-line `categories: [ ... ]` list.
+    fn metadata_entries(content: &str) -> Vec<String> {
+        let mut entries: Vec<String> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+
+            let starts_entry = !line.starts_with(char::is_whitespace)
+                && !line.starts_with([']', '}'])
+                && line.contains(':');
+            match entries.last_mut() {
+                Some(entry) if !starts_entry => {
+                    entry.push('\n');
+                    entry.push_str(line);
+                }
+                _ => entries.push(line.to_string()),
+            }
+        }
+        entries
+    }
+
+    /// Reads a `@relations` block, where each line is a relation type followed by the
+    /// id or title of the other task, e.g. `requires: Set up CI`
+    fn extract_relations_from_ast(ast: &[NorgAST]) -> Vec<TaskRelation> {
+        let mut relations = Vec::new();
+        for node in ast {
+            if let NorgAST::VerbatimRangedTag { name, content, .. } = node
+                && name.iter().map(String::as_str).eq(["relations"])
+            {
+                relations.extend(Self::parse_relations(content));
+            }
+        }
+        relations
+    }
+
+    /// The relations listed in the content of a `@relations` block
+    fn parse_relations(content: &str) -> Vec<TaskRelation> {
+        let mut relations = Vec::new();
+        for line in content.lines() {
+            let Some((key, target)) = line.split_once(':') else {
+                continue;
+            };
+            let target = target.trim();
+            if let Some(relation_type) = RelationType::from_name(key.trim())
+                && !target.is_empty()
+            {
+                relations.push(TaskRelation {
+                    target_task_id: target.to_string(),
+                    relation_type,
+                });
+            }
+        }
+        relations
+    }
+
+    /// Parses RFC 3339 timestamps as well as the `2023-04-23T20:05:21+0100` format Neorg
+    /// generates, falling back to a bare date at midnight UTC
+    fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+        if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+            return Some(timestamp.with_timezone(&Utc));
+        }
+        if let Ok(timestamp) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%z") {
+            return Some(timestamp.with_timezone(&Utc));
+        }
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|datetime| datetime.and_utc())
+    }
+
+    /// A `#tag` at the start of a line is parsed as a carryover tag rather than text,
+    /// so those are collected from the AST
+    fn extract_carryover_tags(ast: &[NorgAST]) -> Vec<String> {
+        let mut tags = Vec::new();
+
+        for node in ast {
+            match node {
+                NorgAST::CarryoverTag {
+                    name,
+                    parameters,
+                    next_object,
+                    ..
+                } => {
+                    tags.push(name.join("."));
+                    tags.extend(
+                        parameters
+                            .iter()
+                            .filter_map(|parameter| parameter.strip_prefix('#'))
+                            .filter(|tag| !tag.is_empty())
+                            .map(str::to_string),
+                    );
+                    tags.extend(Self::extract_carryover_tags(std::slice::from_ref(
+                        next_object.as_ref(),
+                    )));
+                }
+                NorgAST::Heading { content, .. }
+                | NorgAST::NestableDetachedModifier { content, .. } => {
+                    tags.extend(Self::extract_carryover_tags(content));
+                }
+                _ => {}
+            }
+        }
+
+        tags
+    }
+
+    /// Finds inline hashtags such as `#work` or `#project/norgdo` in a piece of text
+    pub fn extract_tags_from_text(text: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+
+        for word in text.split_whitespace() {
+            let Some(tag) = word.strip_prefix('#') else {
+                continue;
+            };
+            let tag = tag.trim_end_matches(|c: char| !c.is_alphanumeric());
+            if !tag.is_empty()
+                && tag
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+                && !tags.iter().any(|t| t == tag)
+            {
+                tags.push(tag.to_string());
             }
         }
-        "Untitled Task".to_string()
+
+        tags
     }
 
-    fn extract_content_from_ast(ast: &[NorgAST]) -> Result<(String, Vec<TodoItem>)> {
+    /// Collects the description and todos. The first heading is the task title, while
+    /// any heading inside the task (or after it) is a section kept in the description
+    /// with its `*`s. List items without a todo status are kept as notes, on the todo
+    /// they follow or in the description when no todo comes before them. That way saving
+    /// doesn't drop, flatten or move them. `in_task` is set when recursing into a heading.
+    fn extract_content_from_ast(ast: &[NorgAST], in_task: bool) -> Result<(String, Vec<TodoItem>)> {
         let mut description_parts = Vec::new();
+        let mut note_lines: Vec<String> = Vec::new(); // Consecutive plain list items
         let mut todos = Vec::new();
         let mut todo_id_counter = 0;
-        let mut found_heading = false;
+        let mut found_heading = in_task;
         let mut in_description = true;
 
         for node in ast {
+            // Carryover tags only annotate the object that follows them, which still
+            // belongs to the task body (the tags are collected separately)
+            let mut node = node;
+            while let NorgAST::CarryoverTag { next_object, .. } = node {
+                node = next_object;
+            }
+
+            let is_list_item = matches!(node, NorgAST::NestableDetachedModifier { .. });
+            if !is_list_item && !note_lines.is_empty() {
+                description_parts.push(note_lines.join("\n"));
+                note_lines.clear();
+            }
+
             match node {
-                NorgAST::Heading { title, content, .. } => {
+                NorgAST::Heading {
+                    level,
+                    title,
+                    content,
+                    ..
+                } => {
+                    if found_heading {
+                        description_parts.push(format!(
+                            "{} {}",
+                            "*".repeat(*level as usize),
+                            Self::paragraph_to_norg(title)
+                        ));
+                    }
                     found_heading = true;
                     in_description = true;
 
-                    // Extract title text for description
-                    let title_text = Self::paragraph_to_string(title);
-                    description_parts.push(title_text);
-
-                    // Process content within heading for todos
-                    let (content_desc, content_todos) = Self::extract_content_from_ast(content)?;
+                    // Process content within heading for its description and todos
+                    let (content_desc, content_todos) =
+                        Self::extract_content_from_ast(content, true)?;
                     if !content_desc.is_empty() {
                         description_parts.push(content_desc);
                     }
                     todos.extend(content_todos);
                 }
-                NorgAST::Paragraph(segments) => {
-                    if found_heading && in_description {
-                        let text = Self::paragraph_to_string(segments);
-                        if !text.trim().is_empty() {
-                            description_parts.push(text);
-                        }
+                NorgAST::Paragraph(segments) if found_heading && in_description => {
+                    let text = Self::paragraph_to_norg(segments);
+                    if !text.trim().is_empty() {
+                        description_parts.push(text);
                     }
                 }
                 NorgAST::NestableDetachedModifier {
-                    modifier_type: NestableDetachedModifier::UnorderedList,
+                    modifier_type:
+                        modifier_type @ (NestableDetachedModifier::UnorderedList
+                        | NestableDetachedModifier::OrderedList),
                     level,
                     text,
                     content,
@@ -81,17 +538,44 @@ impl NorgParser {
                     in_description = false; // Stop collecting description once we hit todos
 
                     // Extract todo from the extensions and text
-                    if let Some(todo) = Self::extract_todo_from_modifier(
+                    let ordered = matches!(modifier_type, NestableDetachedModifier::OrderedList);
+                    match Self::extract_todo_from_modifier(
                         text,
                         *level,
                         extensions,
                         &mut todo_id_counter,
                     )? {
-                        todos.push(todo);
+                        Some(mut todo) => {
+                            todo.ordered = ordered;
+                            todos.push(todo);
+                        }
+                        None => {
+                            if let NorgASTFlat::Paragraph(segments) = text.as_ref() {
+                                let marker = if ordered { "~" } else { "-" };
+                                let note = format!(
+                                    "{} {}",
+                                    marker.repeat((*level).max(1) as usize),
+                                    Self::paragraph_to_norg(segments)
+                                );
+                                match todos.last_mut() {
+                                    Some(todo) => todo.notes.push(note),
+                                    None => note_lines.push(note),
+                                }
+                            }
+                        }
                     }
 
-                    // Recursively process nested todos
-                    let (_, nested_todos) = Self::extract_content_from_ast(content)?;
+                    // Recursively process nested todos. Nested notes before the first
+                    // nested todo come back as the description and follow this item.
+                    let (nested_notes, nested_todos) =
+                        Self::extract_content_from_ast(content, false)?;
+                    if !nested_notes.is_empty() {
+                        let nested_notes = nested_notes.lines().map(str::to_string);
+                        match todos.last_mut() {
+                            Some(todo) => todo.notes.extend(nested_notes),
+                            None => note_lines.extend(nested_notes),
+                        }
+                    }
                     todos.extend(nested_todos);
                 }
                 _ => {
@@ -100,23 +584,29 @@ impl NorgParser {
             }
         }
 
+        if !note_lines.is_empty() {
+            description_parts.push(note_lines.join("\n"));
+        }
+
         let description = description_parts.join("\n\n").trim().to_string();
         Ok((description, todos))
     }
 
     fn extract_todo_from_modifier(
-        text: &Box<NorgASTFlat>,
+        text: &NorgASTFlat,
         level: u16,
         extensions: &Vec<DetachedModifierExtension>,
         todo_id_counter: &mut usize,
     ) -> Result<Option<TodoItem>> {
-        if let NorgASTFlat::Paragraph(segments) = text.as_ref() {
-            let text_content = Self::paragraph_to_string(segments);
+        if let NorgASTFlat::Paragraph(segments) = text {
+            let text_content = Self::paragraph_to_norg(segments);
 
             // Look for Todo extension in the extensions array
             for extension in extensions {
                 // Check if this extension indicates a todo item
-                if let Some(state) = Self::extract_todo_state_from_extension(extension) {
+                if let Some((state, recurrence)) =
+                    Self::extract_todo_state_from_extension(extension)
+                {
                     *todo_id_counter += 1;
 
                     return Ok(Some(TodoItem {
@@ -124,7 +614,10 @@ impl NorgParser {
                         text: text_content,
                         state,
                         level: level as usize,
-                        line_number: 0, // We don't have line numbers from AST
+                        line_number: 0, // Filled in later by `assign_line_numbers`
+                        ordered: false,
+                        recurrence,
+                        notes: Vec::new(),
                     }));
                 }
             }
@@ -132,23 +625,80 @@ impl NorgParser {
         Ok(None)
     }
 
+    /// The AST carries no source positions, so correlate todos back to the source
+    /// with a second pass. Todos are visited in document order, so each one is matched
+    /// against the todo lines following the previous match, preferring an exact text match.
+    fn assign_line_numbers(content: &str, todos: &mut [TodoItem]) {
+        let todo_lines: Vec<(usize, &str)> = content
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| Self::todo_line_text(line).map(|text| (index + 1, text)))
+            .collect();
+
+        let mut cursor = 0;
+        for todo in todos.iter_mut() {
+            let remaining = &todo_lines[cursor..];
+            if remaining.is_empty() {
+                break;
+            }
+
+            let offset = remaining
+                .iter()
+                .position(|(_, text)| text.trim() == todo.text.trim())
+                .unwrap_or(0);
+
+            todo.line_number = remaining[offset].0;
+            cursor += offset + 1;
+        }
+    }
+
+    /// Returns the text of a todo list item line (e.g. `-- (x) Write tests` or
+    /// `~ ( ) Step one`), if it is one
+    fn todo_line_text(line: &str) -> Option<&str> {
+        let trimmed = line.trim_start();
+        let rest = trimmed.trim_start_matches(['-', '~']);
+        if rest.len() == trimmed.len() {
+            return None;
+        }
+
+        let rest = rest.strip_prefix(char::is_whitespace)?.trim_start();
+        let rest = rest.strip_prefix('(')?;
+        // The state may carry data, e.g. the date in `(+ 2024-06-01)`
+        let (state, rest) = rest.split_once(')')?;
+        if state.is_empty() {
+            return None;
+        }
+
+        Some(rest.trim_start())
+    }
+
+    /// The todo state of an extension, along with the recurrence date of `(+ date)`
     fn extract_todo_state_from_extension(
         extension: &DetachedModifierExtension,
-    ) -> Option<TodoState> {
-        // For now, let's match based on debug output patterns we saw
+    ) -> Option<(TodoState, Option<String>)> {
         match extension {
             DetachedModifierExtension::Todo(todo_status) => {
-                // TODO: handle recurring Option<String> for dates
-                match todo_status {
-                    TodoStatus::Done => Some(TodoState::Done),
-                    TodoStatus::Pending => Some(TodoState::Pending),
-                    TodoStatus::Urgent => Some(TodoState::Urgent),
-                    TodoStatus::Undone => Some(TodoState::Undone),
-                    TodoStatus::Paused => Some(TodoState::OnHold),
-                    TodoStatus::Canceled => Some(TodoState::Cancelled),
-                    TodoStatus::NeedsClarification => Some(TodoState::Uncertain),
-                    TodoStatus::Recurring(_) => Some(TodoState::Recurring),
-                }
+                let state = match todo_status {
+                    TodoStatus::Done => TodoState::Done,
+                    TodoStatus::Pending => TodoState::Pending,
+                    TodoStatus::Urgent => TodoState::Urgent,
+                    TodoStatus::Undone => TodoState::Undone,
+                    TodoStatus::Paused => TodoState::OnHold,
+                    TodoStatus::Canceled => TodoState::Cancelled,
+                    TodoStatus::NeedsClarification => TodoState::Uncertain,
+                    TodoStatus::Recurring(date) => {
+                        let date = date
+                            .as_deref()
+                            .map(str::trim)
+                            .filter(|date| !date.is_empty());
+                        return Some((TodoState::Recurring, date.map(str::to_string)));
+                    }
+                    // Keep todos with statuses added to the parser later instead of
+                    // dropping them; they're read as undone and written back as `( )`
+                    #[allow(unreachable_patterns)]
+                    _ => TodoState::Undone,
+                };
+                Some((state, None))
             }
             _ => {
                 // Ignore other extensions
@@ -157,37 +707,44 @@ impl NorgParser {
         }
     }
 
-    fn paragraph_to_string(segments: &[ParagraphSegment]) -> String {
+    /// Turns segments back into Norg markup, so titles, todos and descriptions keep their
+    /// links, inline verbatim and attached modifiers (bold, italic, ...) when written
+    fn paragraph_to_norg(segments: &[ParagraphSegment]) -> String {
         let mut result = String::new();
 
         for segment in segments {
             match segment {
-                ParagraphSegment::Token(token) => match token {
-                    ParagraphSegmentToken::Text(text) => result.push_str(text),
-                    ParagraphSegmentToken::Whitespace => result.push(' '),
-                    ParagraphSegmentToken::Special(c) | ParagraphSegmentToken::Escape(c) => {
-                        result.push(*c);
-                    }
-                },
-                ParagraphSegment::AttachedModifier { content, .. } => {
-                    // For simplicity, just extract the text content from modifiers
-                    result.push_str(&Self::paragraph_to_string(content));
+                ParagraphSegment::Token(token) => Self::push_norg_token(&mut result, token),
+                ParagraphSegment::AttachedModifier {
+                    modifier_type,
+                    content,
+                } => {
+                    result.push(*modifier_type);
+                    result.push_str(&Self::paragraph_to_norg(content));
+                    result.push(*modifier_type);
                 }
                 ParagraphSegment::InlineVerbatim(tokens) => {
+                    result.push('`');
                     for token in tokens {
-                        match token {
-                            ParagraphSegmentToken::Text(text) => result.push_str(text),
-                            ParagraphSegmentToken::Whitespace => result.push(' '),
-                            ParagraphSegmentToken::Special(c)
-                            | ParagraphSegmentToken::Escape(c) => {
-                                result.push(*c);
-                            }
-                        }
+                        Self::push_norg_token(&mut result, token);
                     }
+                    result.push('`');
                 }
-                ParagraphSegment::Link { description, .. } => {
+                ParagraphSegment::Link {
+                    filepath,
+                    targets,
+                    description,
+                } => {
+                    result.push('{');
+                    if let Some(filepath) = filepath {
+                        result.push_str(&format!(":{}:", filepath));
+                    }
+                    for target in targets {
+                        result.push_str(&Self::link_target_to_norg(target));
+                    }
+                    result.push('}');
                     if let Some(desc) = description {
-                        result.push_str(&Self::paragraph_to_string(desc));
+                        result.push_str(&format!("[{}]", Self::paragraph_to_norg(desc)));
                     }
                 }
                 _ => {
@@ -199,11 +756,89 @@ impl NorgParser {
         result
     }
 
+    fn push_norg_token(result: &mut String, token: &ParagraphSegmentToken) {
+        match token {
+            ParagraphSegmentToken::Text(text) => result.push_str(text),
+            ParagraphSegmentToken::Whitespace => result.push(' '),
+            ParagraphSegmentToken::Special(c) => result.push(*c),
+            ParagraphSegmentToken::Escape(c) => {
+                result.push('\\');
+                result.push(*c);
+            }
+        }
+    }
+
+    fn link_target_to_norg(target: &LinkTarget) -> String {
+        match target {
+            LinkTarget::Url(url) => url.clone(),
+            LinkTarget::Path(path) => format!("/ {}", path),
+            LinkTarget::Timestamp(timestamp) => format!("@ {}", timestamp),
+            LinkTarget::Heading { level, title } => format!(
+                "{} {}",
+                "*".repeat(*level as usize),
+                Self::paragraph_to_norg(title)
+            ),
+            LinkTarget::Footnote(title) => format!("^ {}", Self::paragraph_to_norg(title)),
+            LinkTarget::Definition(title) => format!("$ {}", Self::paragraph_to_norg(title)),
+            LinkTarget::Generic(title) => format!("# {}", Self::paragraph_to_norg(title)),
+            LinkTarget::Wiki(title) => format!("? {}", Self::paragraph_to_norg(title)),
+            LinkTarget::Extendable(title) => format!("= {}", Self::paragraph_to_norg(title)),
+        }
+    }
+
     pub fn write_task_file(task: &Task) -> Result<()> {
         let mut content = String::new();
 
+        // Write metadata
+        content.push_str("@document.meta\n");
+        content.push_str(&format!("id: {}\n", task.id));
+        content.push_str(&format!(
+            "created: {}\n",
+            task.created_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ));
+        content.push_str(&format!(
+            "updated: {}\n",
+            task.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ));
+        if let Some(due_date) = task.due_date {
+            content.push_str(&format!("due: {}\n", due_date.format("%Y-%m-%d")));
+        }
+        // Tags already written as hashtags in the description don't need repeating
+        let description_tags = Self::extract_tags_from_text(&task.description);
+        let extra_tags: Vec<&str> = task
+            .tags
+            .iter()
+            .filter(|tag| !description_tags.contains(tag))
+            .map(String::as_str)
+            .collect();
+        if !extra_tags.is_empty() {
+            content.push_str(&format!("tags: {}\n", extra_tags.join(" ")));
+        }
+        for entry in &task.extra_metadata {
+            content.push_str(entry);
+            content.push('\n');
+        }
+        content.push_str("@end\n\n");
+
+        // Write relations
+        if !task.relations.is_empty() {
+            content.push_str("@relations\n");
+            for relation in &task.relations {
+                content.push_str(&format!(
+                    "{}: {}\n",
+                    relation.relation_type.to_name(),
+                    relation.target_task_id
+                ));
+            }
+            content.push_str("@end\n\n");
+        }
+
         // Write title
-        content.push_str(&format!("* {}\n\n", task.title));
+        content.push_str(&format!(
+            "{} {}\n\n",
+            "*".repeat(task.heading_level.max(1)),
+            task.title
+        ));
 
         // Write description
         if !task.description.is_empty() {
@@ -211,18 +846,285 @@ impl NorgParser {
             content.push_str("\n\n");
         }
 
-        // Write todos
+        // Write todos, without stray whitespace around their text
         for todo in &task.todos {
-            let list_prefix = "-".repeat(todo.level.max(1)); // At least one hyphen
-            content.push_str(&format!(
-                "{} ({}) {}\n",
+            let marker = if todo.ordered { "~" } else { "-" };
+            let list_prefix = marker.repeat(todo.level.max(1)); // At least one marker
+            let line = format!(
+                "{} ({}) {}",
                 list_prefix,
-                todo.state.to_norg_char(),
-                todo.text
-            ));
+                todo.state_marker(),
+                todo.text.trim()
+            );
+            content.push_str(line.trim_end());
+            content.push('\n');
+            for note in &todo.notes {
+                content.push_str(note);
+                content.push('\n');
+            }
+        }
+
+        if task.crlf {
+            content = content.replace("\r\n", "\n").replace('\n', "\r\n");
         }
 
         fs::write(&task.file_path, content)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn metadata_survives_a_round_trip() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "@document.meta\ntitle: Groceries\nauthors: someone\ncategories: [\n  home\n  errands\n]\ncreated: 2023-04-23T20:05:21+0100\nupdated: 2023-04-24T08:00:00Z\ndue: 2023-05-01\n@end\n\n* Groceries\n\n- ( ) Milk\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        NorgParser::write_task_file(&task).unwrap();
+        let reparsed = NorgParser::parse_task_file(&path).unwrap();
+
+        assert_eq!(
+            reparsed.created_at,
+            DateTime::parse_from_rfc3339("2023-04-23T19:05:21Z").unwrap()
+        );
+        assert_eq!(reparsed.created_at, task.created_at);
+        assert_eq!(reparsed.updated_at, task.updated_at);
+        assert_eq!(reparsed.due_date, task.due_date);
+        assert_eq!(reparsed.id, task.id);
+        assert_eq!(
+            reparsed.extra_metadata,
+            [
+                "title: Groceries",
+                "authors: someone",
+                "categories: [\n  home\n  errands\n]",
+            ]
+        );
+    }
+
+    #[test]
+    fn todos_get_ascending_line_numbers() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "* Groceries\n\nFor the weekend\n\n- ( ) Milk\n- (x) Bread\n-- ( ) Rye\n- ( ) Milk\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        let line_numbers: Vec<usize> = task.todos.iter().map(|todo| todo.line_number).collect();
+        // The second "Milk" is matched to its own line, not the first one
+        assert_eq!(line_numbers, [5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn mixed_ordered_and_unordered_todos_keep_their_markers() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "* Release\n\n~ ( ) Tag the version\n~~ (x) Bump it\n- ( ) Announce it\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        let kinds: Vec<(&str, bool, usize)> = task
+            .todos
+            .iter()
+            .map(|todo| (todo.text.as_str(), todo.ordered, todo.level))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("Tag the version", true, 1),
+                ("Bump it", true, 2),
+                ("Announce it", false, 1),
+            ]
+        );
+
+        NorgParser::write_task_file(&task).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("~ ( ) Tag the version\n~~ (x) Bump it\n- ( ) Announce it\n"));
+    }
+
+    #[test]
+    fn notes_between_todos_stay_in_place() {
+        let dir = TempDir::new();
+        let content = "* Trip\n\n- Passport is in the drawer\n\n- ( ) Pack\n-- Warm clothes\n-- (x) Charger\n- Check the weather first\n- ( ) Book the train\n";
+        let path = dir.write("task.norg", content);
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        assert_eq!(task.description, "- Passport is in the drawer");
+        assert_eq!(task.todos[0].notes, ["-- Warm clothes"]);
+        assert_eq!(task.todos[1].notes, ["- Check the weather first"]);
+
+        NorgParser::write_task_file(&task).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        let body = &written[written.find("* Trip").unwrap()..];
+        assert_eq!(body, content);
+    }
+
+    #[test]
+    fn heading_levels_survive_a_round_trip() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "** Garden\n\n*** Beds\n\nRaised ones\n\n- ( ) Water\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        assert_eq!(task.heading_level, 2);
+
+        NorgParser::write_task_file(&task).unwrap();
+        let reparsed = NorgParser::parse_task_file(&path).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("** Garden\n\n*** Beds\n"));
+        assert_eq!(reparsed.heading_level, 2);
+        assert_eq!(reparsed.description, task.description);
+    }
+
+    #[test]
+    fn links_keep_their_targets() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "* Docs\n\nRead {https://example.com}[the guide] and {https://norg.dev}\n\n- ( ) Skim it\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        assert_eq!(
+            task.description,
+            "Read {https://example.com}[the guide] and {https://norg.dev}"
+        );
+
+        NorgParser::write_task_file(&task).unwrap();
+        let reparsed = NorgParser::parse_task_file(&path).unwrap();
+        assert_eq!(reparsed.description, task.description);
+    }
+
+    #[test]
+    fn todos_and_titles_keep_their_markup() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "* Ship *v2*\n\n- ( ) read {https://x}[docs]\n- (x) fix `parse`\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        let texts: Vec<&str> = task.todos.iter().map(|todo| todo.text.as_str()).collect();
+        assert_eq!(task.title, "Ship *v2*");
+        assert_eq!(texts, ["read {https://x}[docs]", "fix `parse`"]);
+
+        NorgParser::write_task_file(&task).unwrap();
+        let reparsed = NorgParser::parse_task_file(&path).unwrap();
+        let reparsed_texts: Vec<&str> = reparsed
+            .todos
+            .iter()
+            .map(|todo| todo.text.as_str())
+            .collect();
+        assert_eq!(reparsed.title, task.title);
+        assert_eq!(reparsed_texts, texts);
+    }
+
+    #[test]
+    fn a_leading_bom_is_ignored() {
+        let dir = TempDir::new();
+        let path = dir.write("task.norg", "\u{feff}* Windows task\n\n- ( ) Reboot\n");
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        assert_eq!(task.title, "Windows task");
+        assert_eq!(task.todos.len(), 1);
+        assert_eq!(task.todos[0].line_number, 3);
+
+        let summary = NorgParser::parse_task_summary(&path).unwrap();
+        assert_eq!(summary.title, "Windows task");
+    }
+
+    #[test]
+    fn crlf_files_stay_crlf() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "* Windows task\r\n\r\nSome notes\r\n\r\n- ( ) Reboot\r\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        assert!(task.crlf);
+        NorgParser::write_task_file(&task).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("* Windows task\r\n\r\nSome notes\r\n"));
+        assert_eq!(
+            written.matches('\n').count(),
+            written.matches("\r\n").count()
+        );
+    }
+
+    #[test]
+    fn hand_typed_done_statuses_are_recognized() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "task.norg",
+            "* Chores\n\n- (X) Dishes\n- ( x ) Laundry\n- (x) Trash\n- ( ) Vacuum\n\n@code\n- (X) not a todo\n@end\n",
+        );
+
+        let task = NorgParser::parse_task_file(&path).unwrap();
+        let summary = NorgParser::parse_task_summary(&path).unwrap();
+        for task in [&task, &summary] {
+            let states: Vec<&TodoState> = task.todos.iter().map(|todo| &todo.state).collect();
+            assert_eq!(
+                states,
+                [
+                    &TodoState::Done,
+                    &TodoState::Done,
+                    &TodoState::Done,
+                    &TodoState::Undone
+                ]
+            );
+        }
+
+        // Verbatim blocks are left as written
+        let normalized = NorgParser::read_task_file(&path).unwrap();
+        assert!(normalized.contains("@code\n- (X) not a todo\n@end"));
+        // A line that merely starts with `@` doesn't hide the todos after it
+        let path = dir.write("mention.norg", "* Chores\n\n@someone asked\n- (X) Dishes\n");
+        let normalized = NorgParser::read_task_file(&path).unwrap();
+        assert!(normalized.contains("- (x) Dishes"));
+        // Only the file parser is lenient, a capital X is not a state key
+        assert_eq!(TodoState::from_norg_char('X'), None);
+    }
+
+    #[test]
+    fn summary_agrees_with_the_full_parse() {
+        let fixtures = [
+            "* Groceries\n\n- ( ) Milk\n- (x) Bread\n-- (-) Rye\n",
+            "@document.meta\ntags: home errands\n@end\n\n** Chores #weekly\n\nDo them #soon\n\n~ (!) Dishes\n~~ (?) Laundry\n- (=) Vacuum\n- (_) Windows\n- (+ 2024-06-01) Trash\n",
+            "@relations\nblocks: Groceries\n@end\n\n* Plan\n\n@code\n- ( ) not a todo\n@end\n\n- (X) Shop\n",
+        ];
+        let dir = TempDir::new();
+
+        for (i, fixture) in fixtures.iter().enumerate() {
+            let path = dir.write(&format!("task_{}.norg", i), fixture);
+            let full = NorgParser::parse_task_file(&path).unwrap();
+            let summary = NorgParser::parse_task_summary(&path).unwrap();
+
+            assert_eq!(summary.title, full.title, "{}", fixture);
+            assert_eq!(summary.heading_level, full.heading_level, "{}", fixture);
+            let states = |task: &Task| -> Vec<(TodoState, usize, usize)> {
+                task.todos
+                    .iter()
+                    .map(|todo| (todo.state.clone(), todo.level, todo.line_number))
+                    .collect()
+            };
+            assert_eq!(states(&summary), states(&full), "{}", fixture);
+            let mut summary_tags = summary.tags.clone();
+            let mut full_tags = full.tags.clone();
+            summary_tags.sort();
+            full_tags.sort();
+            assert_eq!(summary_tags, full_tags, "{}", fixture);
+        }
+    }
+}
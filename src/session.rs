@@ -0,0 +1,69 @@
+use crate::app::{BoardMode, FocusedPane};
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use directories::ProjectDirs;
+use norgdo::task_manager::TaskFilter;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Dashboard state remembered between runs, stored in `state.toml` in the platform
+/// state directory (e.g. `~/.local/state/norgdo/state.toml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub focused_pane: FocusedPane,
+    pub selected_task_id: Option<String>,
+    pub ui: UiState,
+}
+
+/// How the dashboard was set up to show tasks, kept in the `[ui]` table of the state
+/// file. Settings missing from an older file keep their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub board_mode: BoardMode,
+    pub show_completed: bool,
+    pub filter: TaskFilter,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            board_mode: BoardMode::default(),
+            show_completed: true,
+            filter: TaskFilter::default(),
+        }
+    }
+}
+
+impl SessionState {
+    /// Loads the saved state. This is only a convenience, so a missing or unreadable
+    /// file simply starts a fresh session.
+    pub fn load() -> Self {
+        Self::state_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = Self::state_path() else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Could not create state directory {}", dir.display()))?;
+        }
+        fs::write(&path, toml::to_string(self)?)
+            .wrap_err_with(|| format!("Could not save session state to {}", path.display()))
+    }
+
+    fn state_path() -> Option<PathBuf> {
+        let dirs = ProjectDirs::from("", "", "norgdo")?;
+        // Only Linux has a dedicated state directory
+        let dir = dirs.state_dir().unwrap_or(dirs.data_local_dir());
+        Some(dir.join("state.toml"))
+    }
+}
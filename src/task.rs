@@ -1,11 +1,13 @@
+use chrono::Datelike;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub enum TodoState {
     Done,
     Pending,
+    #[default]
     Undone,
     Uncertain,
     OnHold,
@@ -15,6 +17,18 @@ pub enum TodoState {
 }
 
 impl TodoState {
+    /// Every todo state, in the order they are listed in the UI
+    pub const ALL: [TodoState; 8] = [
+        TodoState::Undone,
+        TodoState::Pending,
+        TodoState::Done,
+        TodoState::Urgent,
+        TodoState::Uncertain,
+        TodoState::OnHold,
+        TodoState::Cancelled,
+        TodoState::Recurring,
+    ];
+
     pub fn from_norg_char(c: char) -> Option<Self> {
         match c {
             'x' => Some(TodoState::Done),
@@ -29,6 +43,21 @@ impl TodoState {
         }
     }
 
+    /// Parses a state from a human-friendly name such as `urgent` or `on-hold`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "done" => Some(TodoState::Done),
+            "pending" => Some(TodoState::Pending),
+            "undone" | "todo" => Some(TodoState::Undone),
+            "uncertain" => Some(TodoState::Uncertain),
+            "onhold" | "hold" | "paused" => Some(TodoState::OnHold),
+            "cancelled" | "canceled" => Some(TodoState::Cancelled),
+            "recurring" => Some(TodoState::Recurring),
+            "urgent" => Some(TodoState::Urgent),
+            _ => None,
+        }
+    }
+
     pub fn to_norg_char(&self) -> char {
         match self {
             TodoState::Done => 'x',
@@ -59,8 +88,15 @@ impl TodoState {
         matches!(self, TodoState::Done | TodoState::Cancelled)
     }
 
+    /// Whether a todo in this state has been started, which puts its task in progress.
+    /// Paused and unclear todos count too, as someone already looked at them. Recurring
+    /// ones don't: they are set up once and say nothing about the rest of the task, so
+    /// a task with them is only in progress when another todo shows activity.
     pub fn is_in_progress(&self) -> bool {
-        matches!(self, TodoState::Pending | TodoState::Urgent)
+        matches!(
+            self,
+            TodoState::Pending | TodoState::Urgent | TodoState::OnHold | TodoState::Uncertain
+        )
     }
 }
 
@@ -71,32 +107,122 @@ pub struct TodoItem {
     pub state: TodoState,
     pub level: usize, // Indentation level for sub-todos
     pub line_number: usize,
+    #[serde(default)]
+    pub ordered: bool, // Written as an ordered (`~`) rather than unordered (`-`) list item
+    #[serde(default)]
+    pub recurrence: Option<String>, // Date of a recurring todo, as in `(+ 2024-06-01)`
+    #[serde(default)]
+    pub notes: Vec<String>, // Plain list items following the todo, written back right after it
+}
+
+impl TodoItem {
+    /// Creates a todo that hasn't been written to a file yet
+    pub fn new(text: String, state: TodoState, level: usize) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            text,
+            state,
+            level,
+            line_number: 0,
+            ordered: false,
+            recurrence: None,
+            notes: Vec::new(),
+        }
+    }
+
+    /// The contents of the todo's `(...)` status, e.g. `x` or `+ 2024-06-01`.
+    /// The recurrence date is only written while the todo is recurring.
+    pub fn state_marker(&self) -> String {
+        match (&self.state, &self.recurrence) {
+            (TodoState::Recurring, Some(date)) => format!("+ {}", date),
+            (state, _) => state.to_norg_char().to_string(),
+        }
+    }
+
+    /// The state's display name, followed by the date for recurring todos
+    pub fn state_label(&self) -> String {
+        match (&self.state, &self.recurrence) {
+            (TodoState::Recurring, Some(date)) => format!("Recurring {}", date),
+            (state, _) => state.to_string().to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskRelation {
+    /// Id of the related task, or its title when written that way by hand
     pub target_task_id: String,
     pub relation_type: RelationType,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RelationType {
     Related,
     Requires,
     Blocks,
 }
 
+impl RelationType {
+    /// Parses a relation key from the `@relations` block, e.g. `requires` or `blocked-by`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "related" | "relatedto" => Some(RelationType::Related),
+            "requires" | "blockedby" | "dependson" => Some(RelationType::Requires),
+            "blocks" => Some(RelationType::Blocks),
+            _ => None,
+        }
+    }
+
+    /// Key written to the `@relations` block
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            RelationType::Related => "related",
+            RelationType::Requires => "requires",
+            RelationType::Blocks => "blocks",
+        }
+    }
+
+    /// Label from the point of view of the task declaring the relation
+    pub fn to_string(&self) -> &'static str {
+        match self {
+            RelationType::Related => "Related",
+            RelationType::Requires => "Blocked by",
+            RelationType::Blocks => "Blocks",
+        }
+    }
+
+    /// The same relation seen from the other task, e.g. `A requires B` means `B blocks A`
+    pub fn inverse(&self) -> Self {
+        match self {
+            RelationType::Related => RelationType::Related,
+            RelationType::Requires => RelationType::Blocks,
+            RelationType::Blocks => RelationType::Requires,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: String,
     pub title: String,
+    #[serde(default = "Task::default_heading_level")]
+    pub heading_level: usize, // Number of `*`s of the title heading
     pub description: String,
     pub todos: Vec<TodoItem>,
+    pub tags: Vec<String>,
     pub relations: Vec<TaskRelation>,
     pub file_path: PathBuf,
     pub due_date: Option<chrono::NaiveDate>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// `@document.meta` entries norgdo has no use for, such as `title` or `authors`,
+    /// kept as written (continuation lines included) so saving doesn't drop them
+    #[serde(default)]
+    pub extra_metadata: Vec<String>,
+    #[serde(skip)]
+    pub crlf: bool, // Whether the file uses Windows (CRLF) line endings, kept on write
+    #[serde(skip)]
+    pub summary_only: bool, // Loaded by `parse_task_summary`, without the description
 }
 
 impl Task {
@@ -105,16 +231,64 @@ impl Task {
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             title,
+            heading_level: Self::default_heading_level(),
             description: String::new(),
             todos: Vec::new(),
+            tags: Vec::new(),
             relations: Vec::new(),
             file_path,
             due_date: None,
             created_at: now,
             updated_at: now,
+            extra_metadata: Vec::new(),
+            crlf: false,
+            summary_only: false,
         }
     }
 
+    fn default_heading_level() -> usize {
+        1
+    }
+
+    /// Derives a deterministic id from the task's file path, so tasks without a
+    /// persisted id keep the same one across reloads
+    pub fn id_from_path(file_path: &Path) -> String {
+        uuid::Uuid::new_v5(
+            &uuid::Uuid::NAMESPACE_URL,
+            file_path.to_string_lossy().as_bytes(),
+        )
+        .to_string()
+    }
+
+    pub fn has_urgent(&self) -> bool {
+        self.todos
+            .iter()
+            .any(|todo| todo.state == TodoState::Urgent)
+    }
+
+    /// Whether the due date has passed while work is still left
+    pub fn is_overdue(&self, today: chrono::NaiveDate) -> bool {
+        self.due_urgency(today) == Some(DueUrgency::Overdue)
+    }
+
+    /// How close the due date is, or `None` without one. Completed tasks are never
+    /// pressing, so they always count as due later.
+    pub fn due_urgency(&self, today: chrono::NaiveDate) -> Option<DueUrgency> {
+        let due = self.due_date?;
+        let urgency = if self.kanban_category() == KanbanCategory::Completed {
+            DueUrgency::Later
+        } else if due < today {
+            DueUrgency::Overdue
+        } else if due == today {
+            DueUrgency::Today
+        } else if due < today + chrono::Days::new(7) {
+            DueUrgency::ThisWeek
+        } else {
+            DueUrgency::Later
+        };
+        Some(urgency)
+    }
+
     pub fn todo_counts(&self) -> HashMap<TodoState, usize> {
         let mut counts = HashMap::new();
         for todo in &self.todos {
@@ -124,34 +298,82 @@ impl Task {
     }
 
     pub fn completion_percentage(&self) -> f64 {
+        self.completion_percentage_with(CompletionRules::default())
+    }
+
+    pub fn completion_percentage_with(&self, rules: CompletionRules) -> f64 {
         if self.todos.is_empty() {
             return 100.0;
         }
 
-        let completed = self
-            .todos
-            .iter()
-            .filter(|todo| todo.state.is_completed())
-            .count();
+        let (completed, total) = self.completion_counts(rules);
+        if total == 0 {
+            // Every todo was cancelled and cancelled ones don't count
+            return 0.0;
+        }
 
-        (completed as f64 / self.todos.len() as f64) * 100.0
+        (completed as f64 / total as f64) * 100.0
     }
 
-    pub fn kanban_category(&self) -> KanbanCategory {
-        if self.todos.is_empty() {
-            return KanbanCategory::YetToBeDone;
+    /// How many of the todos counted by `rules` are completed, and how many there are
+    pub fn completion_counts(&self, rules: CompletionRules) -> (usize, usize) {
+        match rules.mode {
+            CompletionMode::Flat => {
+                let counted = self.todos.iter().filter(|todo| rules.counts(&todo.state));
+                let completed = counted
+                    .clone()
+                    .filter(|todo| rules.is_completed(&todo.state))
+                    .count();
+                (completed, counted.count())
+            }
+            CompletionMode::Rollup => {
+                let top_level = Self::rolled_up_completion(&self.todos, rules);
+                let completed = top_level.iter().filter(|completed| **completed).count();
+                (completed, top_level.len())
+            }
         }
+    }
 
-        let counts = self.todo_counts();
-        let total = self.todos.len();
-        let completed = *counts.get(&TodoState::Done).unwrap_or(&0)
-            + *counts.get(&TodoState::Cancelled).unwrap_or(&0);
-        let in_progress = *counts.get(&TodoState::Pending).unwrap_or(&0)
-            + *counts.get(&TodoState::Urgent).unwrap_or(&0);
+    /// Whether each counted outermost todo in `todos` is completed, where a todo with
+    /// counted sub-items is completed once all of them are, whatever its own state
+    fn rolled_up_completion(todos: &[TodoItem], rules: CompletionRules) -> Vec<bool> {
+        let mut completed = Vec::new();
+        let mut index = 0;
+        while index < todos.len() {
+            let level = todos[index].level;
+            let end = todos[index + 1..]
+                .iter()
+                .position(|todo| todo.level <= level)
+                .map_or(todos.len(), |offset| index + 1 + offset);
 
-        if completed == total {
+            let children = Self::rolled_up_completion(&todos[index + 1..end], rules);
+            if !children.is_empty() {
+                completed.push(children.into_iter().all(|done| done));
+            } else if rules.counts(&todos[index].state) {
+                completed.push(rules.is_completed(&todos[index].state));
+            }
+            index = end;
+        }
+        completed
+    }
+
+    pub fn kanban_category(&self) -> KanbanCategory {
+        self.kanban_category_with(CompletionRules::default())
+    }
+
+    /// The column a task belongs in. It is completed exactly when its completion
+    /// percentage under the same `rules` is 100%, in progress when a todo was started
+    /// (see `TodoState::is_in_progress`) or completed, and yet to be done otherwise.
+    pub fn kanban_category_with(&self, rules: CompletionRules) -> KanbanCategory {
+        let (completed, total) = self.completion_counts(rules);
+        let started = self
+            .todos
+            .iter()
+            .any(|todo| todo.state.is_in_progress() || rules.is_completed(&todo.state));
+
+        if total > 0 && completed == total {
             KanbanCategory::Completed
-        } else if in_progress > 0 || completed > 0 {
+        } else if started {
             KanbanCategory::InProgress
         } else {
             KanbanCategory::YetToBeDone
@@ -159,19 +381,293 @@ impl Task {
     }
 }
 
+/// How todos add up to a task's completion percentage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionMode {
+    #[default]
+    Flat, // Every todo counts the same, whatever its nesting
+    Rollup, // Only top-level todos count, and a parent is done once all its sub-items are
+}
+
+/// How todos add up to a task's completion, and with it to its kanban column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompletionRules {
+    pub mode: CompletionMode,
+    /// Whether cancelled todos count as completed, instead of not counting at all
+    pub count_cancelled: bool,
+}
+
+impl Default for CompletionRules {
+    fn default() -> Self {
+        Self {
+            mode: CompletionMode::default(),
+            count_cancelled: true,
+        }
+    }
+}
+
+impl CompletionRules {
+    /// Whether a todo in this state counts toward completion at all
+    pub fn counts(&self, state: &TodoState) -> bool {
+        self.count_cancelled || *state != TodoState::Cancelled
+    }
+
+    pub fn is_completed(&self, state: &TodoState) -> bool {
+        state.is_completed() && self.counts(state)
+    }
+}
+
+/// How soon a task is due, relative to today
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueUrgency {
+    Overdue,
+    Today,
+    ThisWeek, // Within the next six days
+    Later,
+}
+
+/// Reads a due date typed by hand, relative to `today`. Besides `YYYY-MM-DD` it
+/// understands `today`, `tomorrow`, `yesterday`, offsets such as `+3d`, `+2w` or `-1m`
+/// (days when the unit is left out), `next week`, `next month` and weekday names. A
+/// bare weekday is the first one from today on, `next monday` the first after today.
+pub fn parse_due_date(
+    input: &str,
+    today: chrono::NaiveDate,
+) -> color_eyre::Result<chrono::NaiveDate> {
+    let input = input.trim().to_lowercase();
+    let invalid = || {
+        color_eyre::eyre::eyre!(
+            "Can't read '{}' as a date, try today, tomorrow, +3d, next monday or YYYY-MM-DD",
+            input
+        )
+    };
+
+    match input.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + chrono::Days::new(1)),
+        "yesterday" => return Ok(today - chrono::Days::new(1)),
+        "next week" => return Ok(today + chrono::Days::new(7)),
+        "next month" => {
+            return today
+                .checked_add_months(chrono::Months::new(1))
+                .ok_or_else(invalid);
+        }
+        _ => {}
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    if let Some(sign @ ('+' | '-')) = input.chars().next() {
+        let offset = input[1..].trim();
+        let (amount, unit) = match offset.find(|c: char| !c.is_ascii_digit()) {
+            Some(index) => offset.split_at(index),
+            None => (offset, "d"),
+        };
+        let amount: u32 = amount.parse().map_err(|_| invalid())?;
+        let date = match (unit.trim(), sign) {
+            ("d", '+') => today.checked_add_days(chrono::Days::new(amount.into())),
+            ("d", _) => today.checked_sub_days(chrono::Days::new(amount.into())),
+            ("w", '+') => today.checked_add_days(chrono::Days::new(u64::from(amount) * 7)),
+            ("w", _) => today.checked_sub_days(chrono::Days::new(u64::from(amount) * 7)),
+            ("m", '+') => today.checked_add_months(chrono::Months::new(amount)),
+            ("m", _) => today.checked_sub_months(chrono::Months::new(amount)),
+            _ => None,
+        };
+        return date.ok_or_else(invalid);
+    }
+
+    let (weekday, skip_today) = match input.strip_prefix("next ") {
+        Some(weekday) => (weekday.trim(), true),
+        None => (input.as_str(), false),
+    };
+    let weekday: chrono::Weekday = weekday.parse().map_err(|_| invalid())?;
+    let mut days_ahead =
+        (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    if skip_today && days_ahead == 0 {
+        days_ahead = 7;
+    }
+    Ok(today + chrono::Days::new(days_ahead.into()))
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum KanbanCategory {
     YetToBeDone,
     InProgress,
     Completed,
+    Custom(String), // A column from the config, by name
 }
 
 impl KanbanCategory {
-    pub fn to_string(&self) -> &'static str {
+    /// The columns every board has, in board order around any extra ones
+    pub const BUILT_IN: [KanbanCategory; 3] = [
+        KanbanCategory::YetToBeDone,
+        KanbanCategory::InProgress,
+        KanbanCategory::Completed,
+    ];
+
+    pub fn to_string(&self) -> &str {
         match self {
             KanbanCategory::YetToBeDone => "Yet to be Done",
             KanbanCategory::InProgress => "In Progress",
             KanbanCategory::Completed => "Completed",
+            KanbanCategory::Custom(name) => name,
+        }
+    }
+}
+
+/// An extra kanban column, such as "Blocked". A task that isn't completed goes into
+/// the first of these columns with one of its todos' states, instead of into
+/// Yet to be Done or In Progress.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KanbanColumn {
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_state_names")]
+    pub states: Vec<TodoState>,
+}
+
+impl KanbanColumn {
+    pub fn matches(&self, task: &Task) -> bool {
+        task.todos
+            .iter()
+            .any(|todo| self.states.contains(&todo.state))
+    }
+}
+
+/// Reads states by the names `TodoState::from_name` understands, e.g. `on_hold`
+fn deserialize_state_names<'de, D>(deserializer: D) -> Result<Vec<TodoState>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|name| {
+            TodoState::from_name(name)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown todo state '{}'", name)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_with(states: &[TodoState]) -> Task {
+        let mut task = Task::new("Task".to_string(), PathBuf::from("task.norg"));
+        task.todos = states
+            .iter()
+            .map(|state| TodoItem::new("Todo".to_string(), state.clone(), 1))
+            .collect();
+        task
+    }
+
+    fn category(states: &[TodoState]) -> KanbanCategory {
+        task_with(states).kanban_category_with(CompletionRules::default())
+    }
+
+    #[test]
+    fn each_started_state_puts_a_task_in_progress() {
+        use TodoState::*;
+
+        for state in [Pending, Urgent, OnHold, Uncertain] {
+            assert_eq!(
+                category(std::slice::from_ref(&state)),
+                KanbanCategory::InProgress,
+                "{:?}",
+                state
+            );
+            assert_eq!(
+                category(&[Undone, state.clone()]),
+                KanbanCategory::InProgress,
+                "{:?}",
+                state
+            );
+        }
+    }
+
+    #[test]
+    fn recurring_todos_need_other_activity() {
+        use TodoState::*;
+
+        assert_eq!(category(&[Recurring]), KanbanCategory::YetToBeDone);
+        assert_eq!(category(&[Recurring, Undone]), KanbanCategory::YetToBeDone);
+        assert_eq!(category(&[Recurring, Pending]), KanbanCategory::InProgress);
+        assert_eq!(category(&[Recurring, Done]), KanbanCategory::InProgress);
+        assert_eq!(
+            category(&[Recurring, OnHold, Undone]),
+            KanbanCategory::InProgress
+        );
+    }
+
+    #[test]
+    fn completion_decides_the_other_columns() {
+        use TodoState::*;
+
+        assert_eq!(category(&[]), KanbanCategory::YetToBeDone);
+        assert_eq!(category(&[Undone, Undone]), KanbanCategory::YetToBeDone);
+        assert_eq!(category(&[Done, Undone]), KanbanCategory::InProgress);
+        assert_eq!(category(&[Done, Cancelled]), KanbanCategory::Completed);
+
+        // Without counting cancelled todos, only the done ones decide
+        let rules = CompletionRules {
+            count_cancelled: false,
+            ..CompletionRules::default()
+        };
+        let task = task_with(&[Cancelled]);
+        assert_eq!(
+            task.kanban_category_with(rules),
+            KanbanCategory::YetToBeDone
+        );
+        let task = task_with(&[Done, Cancelled]);
+        assert_eq!(task.kanban_category_with(rules), KanbanCategory::Completed);
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn due_dates_relative_to_today() {
+        // A Monday
+        let today = date(2025, 3, 31);
+        let parse = |input| parse_due_date(input, today).unwrap();
+
+        assert_eq!(parse("today"), today);
+        assert_eq!(parse(" Tomorrow "), date(2025, 4, 1));
+        assert_eq!(parse("+3d"), date(2025, 4, 3));
+        assert_eq!(parse("+3"), date(2025, 4, 3));
+        assert_eq!(parse("+2w"), date(2025, 4, 14));
+        assert_eq!(parse("-1m"), date(2025, 2, 28));
+        assert_eq!(parse("+1m"), date(2025, 4, 30));
+        assert_eq!(parse("-2d"), date(2025, 3, 29));
+    }
+
+    #[test]
+    fn due_dates_by_weekday() {
+        let monday = date(2025, 3, 31);
+        let parse = |input| parse_due_date(input, monday).unwrap();
+
+        // A bare weekday can be today, `next` always looks past it
+        assert_eq!(parse("monday"), monday);
+        assert_eq!(parse("next monday"), date(2025, 4, 7));
+        assert_eq!(parse("wednesday"), date(2025, 4, 2));
+        assert_eq!(parse("next wed"), date(2025, 4, 2));
+        assert_eq!(parse("sunday"), date(2025, 4, 6));
+    }
+
+    #[test]
+    fn iso_and_invalid_due_dates() {
+        let today = date(2025, 3, 31);
+
+        assert_eq!(
+            parse_due_date("2025-12-24", today).unwrap(),
+            date(2025, 12, 24)
+        );
+        for input in ["", "2025-02-30", "soon", "+3x", "+d", "next", "24/12/2025"] {
+            let error = parse_due_date(input, today).unwrap_err();
+            assert!(error.to_string().contains("Can't read"), "{}", input);
         }
     }
 }
@@ -1,108 +1,623 @@
 use crate::parser::NorgParser;
-use crate::task::{KanbanCategory, Task, TodoState};
+use crate::task::{
+    CompletionRules, KanbanCategory, KanbanColumn, RelationType, Task, TaskRelation, TodoItem,
+    TodoState,
+};
 use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
 use directories::ProjectDirs;
-use std::collections::HashMap;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::OsString;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Restricts which tasks are shown on the dashboard
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskFilter {
+    pub tag: Option<String>,
+    /// Space separated query terms as understood by search, all of which must match
+    pub query: String,
+    pub agenda: Option<AgendaWindow>,
+}
+
+/// Restricts the board to tasks due soon, earliest first
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AgendaWindow {
+    pub days: u32, // How many days ahead of today to include
+    pub include_overdue: bool,
+}
+
+impl AgendaWindow {
+    pub fn contains(&self, task: &Task, today: chrono::NaiveDate) -> bool {
+        task.due_date.is_some_and(|due| {
+            due <= today + chrono::Days::new(self.days.into())
+                && (self.include_overdue || due >= today)
+        })
+    }
+}
+
+impl TaskFilter {
+    pub fn is_active(&self) -> bool {
+        self.tag.is_some() || !self.query.trim().is_empty() || self.agenda.is_some()
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        let tag_matches = self
+            .tag
+            .as_ref()
+            .is_none_or(|tag| task.tags.iter().any(|t| t == tag));
+        let agenda_matches = self
+            .agenda
+            .is_none_or(|agenda| agenda.contains(task, chrono::Local::now().date_naive()));
+
+        tag_matches
+            && agenda_matches
+            && self
+                .query
+                .split_whitespace()
+                .all(|term| TaskManager::term_matches(task, term))
+    }
+
+    /// Orders tasks for display: by due date in agenda mode, as loaded otherwise
+    fn sort(&self, tasks: &mut [&Task]) {
+        if self.agenda.is_some() {
+            tasks.sort_by_key(|task| task.due_date);
+        }
+    }
+}
+
+/// Totals across all tasks, as printed by `norgdo stats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub total_tasks: usize,
+    /// Tasks per kanban column, in the order of the board
+    pub columns: Vec<(KanbanCategory, usize)>,
+    /// Completed todos and all todos counted by the completion rules, across all tasks
+    pub completed_todos: usize,
+    pub counted_todos: usize,
+    /// Todos per state, in the order states are listed in the UI
+    pub todo_counts: Vec<(TodoState, usize)>,
+}
+
+impl Stats {
+    /// Share of all counted todos that are completed, 100% when there are none like
+    /// for a single task
+    pub fn completion_percentage(&self) -> f64 {
+        if self.counted_todos == 0 {
+            return 100.0;
+        }
+        (self.completed_todos as f64 / self.counted_todos as f64) * 100.0
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Tasks: {}", self.total_tasks)?;
+        for (category, count) in &self.columns {
+            writeln!(f, "  {:<16} {}", category.to_string(), count)?;
+        }
+
+        writeln!(
+            f,
+            "\nCompletion: {:.0}% ({}/{} todos)",
+            self.completion_percentage(),
+            self.completed_todos,
+            self.counted_todos
+        )?;
+        for (state, count) in &self.todo_counts {
+            writeln!(f, "  {:<16} {}", state.to_string(), count)?;
+        }
+        Ok(())
+    }
+}
 
 pub struct TaskManager {
     tasks: Vec<Task>,
     data_dir: PathBuf,
+    parse_errors: Vec<(PathBuf, String)>, // Files skipped by the last load and why
+    load_warnings: Vec<String>,           // Problems the last load worked around
+    dirty: HashSet<String>,               // Ids of tasks changed in memory but not on disk
+    auto_save: bool,                      // Write changes right away instead of on save
+    completion_rules: CompletionRules,    // Decide when a task counts as completed
+    columns: Vec<KanbanColumn>,           // Extra columns from the config, in board order
+    written: Vec<PathBuf>,                // Files written since `take_written_files`
 }
 
 impl TaskManager {
-    pub fn new() -> Result<Self> {
-        let data_dir = Self::get_data_directory()?;
+    pub fn new(dir_override: Option<PathBuf>) -> Result<Self> {
+        let mut manager = Self::without_tasks(dir_override)?;
+        manager.load_tasks()?;
+        Ok(manager)
+    }
+
+    /// Like `new`, but doesn't load any tasks yet, e.g. so they can be loaded on
+    /// another thread (see `unloaded`)
+    pub fn without_tasks(dir_override: Option<PathBuf>) -> Result<Self> {
+        let data_dir = Self::get_data_directory(dir_override, |name| std::env::var_os(name))?;
 
         // Ensure data directory exists
         if !data_dir.exists() {
-            fs::create_dir_all(&data_dir)?;
+            fs::create_dir_all(&data_dir).wrap_err_with(|| {
+                format!("Could not create data directory {}", data_dir.display())
+            })?;
         }
 
-        let mut manager = Self {
-            tasks: Vec::new(),
-            data_dir,
-        };
+        Self::at(data_dir)
+    }
 
+    /// Loads tasks without creating the data directory, for read-only uses such as
+    /// exporting. A missing directory simply has no tasks.
+    pub fn open(dir_override: Option<PathBuf>) -> Result<Self> {
+        let data_dir = Self::get_data_directory(dir_override, |name| std::env::var_os(name))?;
+        let mut manager = Self::at(data_dir)?;
         manager.load_tasks()?;
         Ok(manager)
     }
 
-    fn get_data_directory() -> Result<PathBuf> {
+    fn at(data_dir: PathBuf) -> Result<Self> {
+        if data_dir.exists() && !data_dir.is_dir() {
+            return Err(color_eyre::eyre::eyre!(
+                "Data directory {} is not a directory",
+                data_dir.display()
+            ));
+        }
+
+        Ok(Self {
+            tasks: Vec::new(),
+            data_dir,
+            parse_errors: Vec::new(),
+            load_warnings: Vec::new(),
+            dirty: HashSet::new(),
+            auto_save: true,
+            completion_rules: CompletionRules::default(),
+            columns: Vec::new(),
+            written: Vec::new(),
+        })
+    }
+
+    /// A manager for the same directory with the same settings but no tasks, to load
+    /// them in the background and swap the result in once done
+    pub fn unloaded(&self) -> Self {
+        Self {
+            tasks: Vec::new(),
+            data_dir: self.data_dir.clone(),
+            parse_errors: Vec::new(),
+            load_warnings: Vec::new(),
+            dirty: HashSet::new(),
+            auto_save: self.auto_save,
+            completion_rules: self.completion_rules,
+            columns: self.columns.clone(),
+            written: Vec::new(),
+        }
+    }
+
+    /// Resolves the data directory, in order of precedence: the `--dir` flag,
+    /// `$NORGDO_DIR`, `$XDG_DATA_HOME/norgdo` (Linux only), the platform default from
+    /// `directories` and finally `~/.local/share/norgdo`. The variables norgdo checks
+    /// itself are read through `env` (`std::env::var_os` outside of tests), while the
+    /// `directories` default always looks at the real environment.
+    fn get_data_directory(
+        dir_override: Option<PathBuf>,
+        env: impl Fn(&str) -> Option<OsString>,
+    ) -> Result<PathBuf> {
+        if let Some(dir) = dir_override {
+            return Ok(dir);
+        }
+
+        let var = |name: &str| {
+            env(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+
+        if let Some(dir) = var("NORGDO_DIR") {
+            return Ok(dir);
+        }
+
+        // The XDG spec says relative paths are invalid and must be ignored
+        if cfg!(target_os = "linux")
+            && let Some(data_home) = var("XDG_DATA_HOME").filter(|dir| dir.is_absolute())
+        {
+            return Ok(data_home.join("norgdo"));
+        }
+
         if let Some(proj_dirs) = ProjectDirs::from("", "", "norgdo") {
             Ok(proj_dirs.data_dir().to_path_buf())
         } else {
             // Fallback to ~/.local/share/norgdo
-            let home = dirs::home_dir()
+            let home = var("HOME")
+                .or_else(dirs::home_dir)
                 .ok_or_else(|| color_eyre::eyre::eyre!("Could not determine home directory"))?;
             Ok(home.join(".local").join("share").join("norgdo"))
         }
     }
 
+    /// Loads every task file in the data directory, including the ones in project
+    /// subdirectories
     pub fn load_tasks(&mut self) -> Result<()> {
+        self.load_with(NorgParser::parse_task_file)
+    }
+
+    /// Like `load_tasks`, but only reads the titles, todos and metadata the board shows,
+    /// which is much faster on big directories. The rest of a task is read by
+    /// `load_full` before it is shown or changed.
+    pub fn load_summaries(&mut self) -> Result<()> {
+        self.load_with(NorgParser::parse_task_summary)
+    }
+
+    fn load_with(&mut self, parse: fn(&Path) -> Result<Task>) -> Result<()> {
         self.tasks.clear();
+        self.parse_errors.clear();
+        self.load_warnings.clear();
+        self.dirty.clear();
 
         if !self.data_dir.exists() {
             return Ok(());
         }
 
-        for entry in fs::read_dir(&self.data_dir)? {
+        let mut files = Vec::new();
+        self.find_task_files(&self.data_dir, &mut HashSet::new(), &mut files)?;
+        for (path, result) in files.iter().zip(Self::parse_files(&files, parse)) {
+            match result {
+                Ok(task) => self.tasks.push(task),
+                Err(e) => self.parse_errors.push((path.clone(), e.to_string())),
+            }
+        }
+        self.dedupe_ids();
+
+        self.parse_errors.sort();
+        Ok(())
+    }
+
+    /// Lists the task files in a directory and its subdirectories, skipping the archive
+    /// and hidden directories. Symlinked directories are not followed to avoid loops.
+    /// Files already in `seen` (by canonical path, e.g. through a symlink) are skipped,
+    /// so a task never gets two cards that overwrite each other.
+    fn find_task_files(
+        &self,
+        dir: &Path,
+        seen: &mut HashSet<PathBuf>,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_file() && path.extension().map_or(false, |ext| ext == "norg") {
-                match NorgParser::parse_task_file(&path) {
-                    Ok(task) => self.tasks.push(task),
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse task file {:?}: {}", path, e);
+            if entry.file_type()?.is_dir() {
+                let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if !is_hidden && path != self.archived_dir() && path != self.templates_dir() {
+                    self.find_task_files(&path, seen, files)?;
+                }
+            } else if path.is_file() && path.extension().is_some_and(|ext| ext == "norg") {
+                let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+                if seen.insert(canonical) {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses files on all available cores, which makes startup with thousands of
+    /// tasks much faster. Results are in the same order as `paths`.
+    fn parse_files(paths: &[PathBuf], parse: fn(&Path) -> Result<Task>) -> Vec<Result<Task>> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = paths.len().div_ceil(threads).max(1);
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = paths
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(|| chunk.iter().map(|path| parse(path)).collect::<Vec<_>>())
+                })
+                .collect();
+            handles
+                .into_iter()
+                // A panic in the parser propagates just like it would without threads
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
+    }
+
+    /// Fully parses a task loaded by `load_summaries`, e.g. when it is opened. The task
+    /// keeps its id, as it may have been changed by `dedupe_ids`. Does nothing for
+    /// tasks that are already fully loaded.
+    pub fn load_full(&mut self, task_id: &str) -> Result<()> {
+        let Some(task) = self
+            .tasks
+            .iter_mut()
+            .find(|t| t.id == task_id && t.summary_only)
+        else {
+            return Ok(());
+        };
+
+        let mut full = NorgParser::parse_task_file(&task.file_path)
+            .wrap_err_with(|| format!("Could not read {}", task.file_path.display()))?;
+        full.id = task.id.clone();
+        full.file_path = task.file_path.clone();
+        *task = full;
+        Ok(())
+    }
+
+    /// Fully parses every task loaded by `load_summaries`, e.g. before searching their
+    /// descriptions. Tasks that fail to parse stay summaries, and the first error is
+    /// returned once the others are loaded.
+    pub fn load_full_all(&mut self) -> Result<()> {
+        let parsed = Self::parse_full(&self.summaries());
+        self.insert_full(parsed)
+    }
+
+    /// The ids and paths of the tasks loaded by `load_summaries`, to be fully parsed by
+    /// `parse_full`
+    pub fn summaries(&self) -> Vec<(String, PathBuf)> {
+        self.tasks
+            .iter()
+            .filter(|task| task.summary_only)
+            .map(|task| (task.id.clone(), task.file_path.clone()))
+            .collect()
+    }
+
+    /// Fully parses the tasks listed by `summaries`, keeping their ids. It doesn't need
+    /// the manager, so it can run on another thread while the summaries stay in use.
+    pub fn parse_full(summaries: &[(String, PathBuf)]) -> Vec<Result<Task>> {
+        let paths: Vec<PathBuf> = summaries.iter().map(|(_, path)| path.clone()).collect();
+        summaries
+            .iter()
+            .zip(Self::parse_files(&paths, NorgParser::parse_task_file))
+            .map(|((id, path), result)| {
+                let mut full =
+                    result.wrap_err_with(|| format!("Could not read {}", path.display()))?;
+                full.id = id.clone();
+                full.file_path = path.clone();
+                Ok(full)
+            })
+            .collect()
+    }
+
+    /// Puts tasks parsed by `parse_full` in place of their summaries. Tasks that were
+    /// fully loaded, reloaded or removed in the meantime are left alone. Tasks that
+    /// failed to parse stay summaries, and the first error is returned.
+    pub fn insert_full(&mut self, parsed: Vec<Result<Task>>) -> Result<()> {
+        let mut first_error = None;
+        for result in parsed {
+            match result {
+                Ok(full) => {
+                    if let Some(task) = self.tasks.iter_mut().find(|task| {
+                        task.summary_only && task.id == full.id && task.file_path == full.file_path
+                    }) {
+                        *task = full;
                     }
                 }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Re-reads a single task file after it changed on disk, leaving every other task
+    /// as it is. A changed file replaces its task in place, a new one is added and
+    /// a removed (or no longer parsable) one drops off the board. Paths outside of
+    /// the loaded task files, such as the archive, are ignored. Its warnings are added
+    /// to `load_warnings`, see `clear_load_warnings`.
+    pub fn reload_task(&mut self, path: &Path) -> Result<()> {
+        let path = std::path::absolute(path)?;
+        let is_path = |other: &Path| std::path::absolute(other).is_ok_and(|other| other == path);
+        if !self.is_task_file(&path) {
+            return Ok(());
+        }
+
+        let index = self.tasks.iter().position(|task| is_path(&task.file_path));
+        self.parse_errors
+            .retain(|(error_path, _)| !is_path(error_path));
+
+        // Another loaded file may be the same one through a symlink
+        let canonical = fs::canonicalize(&path).ok();
+        let is_duplicate = canonical.is_some()
+            && self.tasks.iter().any(|task| {
+                !is_path(&task.file_path) && fs::canonicalize(&task.file_path).ok() == canonical
+            });
+        if !path.is_file() || is_duplicate {
+            if let Some(index) = index {
+                let task = self.tasks.remove(index);
+                self.dirty.remove(&task.id);
             }
+            return Ok(());
         }
 
+        match NorgParser::parse_task_file(&path) {
+            Ok(mut task) => {
+                // Keep the path as loaded, so it matches the paths of the other tasks
+                if let Some(index) = index {
+                    task.file_path = self.tasks[index].file_path.clone();
+                }
+                let id_taken = self
+                    .tasks
+                    .iter()
+                    .enumerate()
+                    .any(|(i, other)| Some(i) != index && other.id == task.id);
+                if id_taken {
+                    self.load_warnings.push(format!(
+                        "{} has the same id as another task, using a new id for it",
+                        task.file_path.display()
+                    ));
+                    task.id = Task::id_from_path(&task.file_path);
+                }
+
+                match index {
+                    Some(index) => {
+                        let old = std::mem::replace(&mut self.tasks[index], task);
+                        self.dirty.remove(&old.id);
+                    }
+                    None => self.tasks.push(task),
+                }
+            }
+            Err(e) => {
+                if let Some(index) = index {
+                    let task = self.tasks.remove(index);
+                    self.dirty.remove(&task.id);
+                }
+                self.parse_errors.push((path, e.to_string()));
+                self.parse_errors.sort();
+            }
+        }
         Ok(())
     }
 
+    /// Whether `load_tasks` would load this path: a `.norg` file in the data directory,
+    /// outside of the archive, the templates and hidden directories
+    fn is_task_file(&self, path: &Path) -> bool {
+        let Ok(data_dir) = std::path::absolute(&self.data_dir) else {
+            return false;
+        };
+        let Ok(relative) = path.strip_prefix(&data_dir) else {
+            return false;
+        };
+        let mut dirs = relative.parent().into_iter().flat_map(Path::components);
+        let in_skipped_dir = dirs
+            .any(|component| component.as_os_str().to_string_lossy().starts_with('.'))
+            || relative.starts_with("archive")
+            || relative.starts_with("templates");
+
+        path.extension().is_some_and(|ext| ext == "norg") && !in_skipped_dir
+    }
+
+    /// Gives tasks whose persisted id is already taken (e.g. a copied file) an id
+    /// derived from their path, so actions on one card don't land on the other
+    fn dedupe_ids(&mut self) {
+        let mut ids: HashMap<String, PathBuf> = HashMap::new();
+        for task in &mut self.tasks {
+            if let Some(first) = ids.get(&task.id) {
+                self.load_warnings.push(format!(
+                    "{} has the same id as {}, using a new id for it",
+                    task.file_path.display(),
+                    first.display()
+                ));
+                task.id = Task::id_from_path(&task.file_path);
+            }
+            ids.entry(task.id.clone())
+                .or_insert_with(|| task.file_path.clone());
+        }
+    }
+
     pub fn create_task(&mut self, title: String) -> Result<&Task> {
-        let filename = Self::sanitize_filename(&title);
-        let file_path = self.data_dir.join(format!("{}.norg", filename));
+        let file_path = self.new_file_path(&self.data_dir, &title);
 
         let task = Task::new(title, file_path);
         NorgParser::write_task_file(&task)?;
+        self.written.push(task.file_path.clone());
 
         self.tasks.push(task);
         Ok(self.tasks.last().unwrap())
     }
 
+    /// Creates a task from the wizard. Todo levels follow the parser's convention,
+    /// where 1 is a top-level item (`-`) and each extra level adds a hyphen.
     pub fn create_task_with_details(
         &mut self,
         title: String,
         description: String,
-        initial_todos: Vec<String>,
+        due_date: Option<chrono::NaiveDate>,
+        initial_todos: Vec<TodoItem>,
     ) -> Result<&Task> {
-        use crate::task::{TodoItem, TodoState};
-
-        let filename = Self::sanitize_filename(&title);
-        let file_path = self.data_dir.join(format!("{}.norg", filename));
+        let file_path = self.new_file_path(&self.data_dir, &title);
 
         let mut task = Task::new(title, file_path);
-        task.description = description;
+        task.description = description.trim().to_string();
+        task.due_date = due_date;
 
         // Add initial TODO items
-        for (index, todo_text) in initial_todos.iter().enumerate() {
-            if !todo_text.trim().is_empty() {
-                task.todos.push(TodoItem {
-                    id: uuid::Uuid::new_v4().to_string(),
-                    text: todo_text.trim().to_string(),
-                    state: TodoState::Undone,
-                    level: 0,               // Top-level todos
-                    line_number: index + 2, // Start after title and empty line
-                });
-            }
-        }
+        task.todos = initial_todos
+            .into_iter()
+            .filter(|todo| !todo.text.trim().is_empty())
+            .map(|todo| TodoItem {
+                text: todo.text.trim().to_string(),
+                level: todo.level.max(1),
+                ..todo
+            })
+            .collect();
 
         NorgParser::write_task_file(&task)?;
+        self.written.push(task.file_path.clone());
+
+        self.tasks.push(task);
+        Ok(self.tasks.last().unwrap())
+    }
+
+    /// Copies a task into a new one next to it, e.g. to reuse a checklist. The copy
+    /// keeps the description, tags and todos (all back to undone) under a "(copy)" title.
+    pub fn duplicate_task(&mut self, task_id: &str) -> Result<&Task> {
+        self.load_full(task_id)?;
+        let original = self
+            .tasks
+            .iter()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| color_eyre::eyre::eyre!("No task with id '{}'", task_id))?;
+
+        let title = format!("{} (copy)", original.title);
+        let dir = original.file_path.parent().unwrap_or(&self.data_dir);
+        let mut task = Task::new(title.clone(), self.new_file_path(dir, &title));
+        task.heading_level = original.heading_level;
+        task.crlf = original.crlf;
+        task.description = original.description.clone();
+        task.tags = original.tags.clone();
+        task.todos = Self::reset_todos(&original.todos);
+
+        NorgParser::write_task_file(&task)?;
+        self.written.push(task.file_path.clone());
+
+        self.tasks.push(task);
+        Ok(self.tasks.last().unwrap())
+    }
+
+    /// Directory holding task templates. Templates are plain `.norg` task files that
+    /// never show up on the board.
+    pub fn templates_dir(&self) -> PathBuf {
+        self.data_dir.join("templates")
+    }
+
+    /// Names of the available templates (their file names without extension), sorted
+    pub fn list_templates(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.templates_dir()) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "norg"))
+            .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Creates a task from the template `name`, keeping its description, tags and
+    /// todos (all back to undone) under the given title
+    pub fn create_from_template(&mut self, name: &str, title: String) -> Result<&Task> {
+        let template_path = self.templates_dir().join(format!("{}.norg", name));
+        let template = NorgParser::parse_task_file(&template_path)
+            .wrap_err_with(|| format!("Could not read template '{}'", name))?;
+
+        let mut task = Task::new(title.clone(), self.new_file_path(&self.data_dir, &title));
+        task.heading_level = template.heading_level;
+        task.crlf = template.crlf;
+        task.description = template.description;
+        task.tags = template.tags;
+        task.todos = Self::reset_todos(&template.todos);
+
+        NorgParser::write_task_file(&task)?;
+        self.written.push(task.file_path.clone());
 
         self.tasks.push(task);
         Ok(self.tasks.last().unwrap())
@@ -110,11 +625,91 @@ impl TaskManager {
 
     pub fn save_task(&mut self, task_id: &str) -> Result<()> {
         if let Some(task) = self.tasks.iter().find(|t| t.id == task_id) {
+            // Writing a summary would drop everything it doesn't hold, like the description
+            if task.summary_only {
+                return Err(color_eyre::eyre::eyre!(
+                    "'{}' is not fully loaded and can't be saved",
+                    task.title
+                ));
+            }
             NorgParser::write_task_file(task)?;
+            self.written.push(task.file_path.clone());
+            self.dirty.remove(task_id);
         }
         Ok(())
     }
 
+    /// Writes every task with unsaved changes
+    pub fn save_all(&mut self) -> Result<()> {
+        let dirty: Vec<String> = self.dirty.iter().cloned().collect();
+        for task_id in dirty {
+            self.save_task(&task_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_auto_save(&mut self, auto_save: bool) {
+        self.auto_save = auto_save;
+    }
+
+    pub fn set_completion_rules(&mut self, rules: CompletionRules) {
+        self.completion_rules = rules;
+    }
+
+    pub fn completion_rules(&self) -> CompletionRules {
+        self.completion_rules
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<KanbanColumn>) {
+        self.columns = columns;
+    }
+
+    /// The extra columns shown between In Progress and Completed
+    pub fn columns(&self) -> &[KanbanColumn] {
+        &self.columns
+    }
+
+    /// The kanban column of a task under the configured completion rules and columns
+    pub fn category_of(&self, task: &Task) -> KanbanCategory {
+        let category = task.kanban_category_with(self.completion_rules);
+        if category == KanbanCategory::Completed {
+            return category;
+        }
+        self.columns
+            .iter()
+            .find(|column| column.matches(task))
+            .map_or(category, |column| {
+                KanbanCategory::Custom(column.name.clone())
+            })
+    }
+
+    /// Records that a task changed in memory, bumping its `updated_at` and writing it
+    /// right away when auto-saving. Every mutation goes through here so they all
+    /// respect the setting.
+    fn task_changed(&mut self, task_id: &str) -> Result<()> {
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            task.updated_at = chrono::Utc::now();
+        }
+        self.dirty.insert(task_id.to_string());
+        if self.auto_save {
+            self.save_task(task_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_dirty(&self, task_id: &str) -> bool {
+        self.dirty.contains(task_id)
+    }
+
+    /// Number of tasks whose in-memory state differs from their file
+    pub fn unsaved_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
     pub fn delete_task(&mut self, task_id: &str) -> Result<()> {
         if let Some(index) = self.tasks.iter().position(|t| t.id == task_id) {
             let task = &self.tasks[index];
@@ -126,47 +721,425 @@ impl TaskManager {
         Ok(())
     }
 
+    /// Where archived task files are moved to, an `archive` folder in the data directory
+    pub fn archived_dir(&self) -> PathBuf {
+        self.data_dir.join("archive")
+    }
+
+    /// Moves a task file into the archive, taking it off the board. Unsaved changes are
+    /// written first so they are archived too.
+    pub fn archive_task(&mut self, task_id: &str) -> Result<()> {
+        let Some(index) = self.tasks.iter().position(|t| t.id == task_id) else {
+            return Ok(());
+        };
+        if self.dirty.contains(task_id) {
+            self.save_task(task_id)?;
+        }
+
+        let archived_dir = self.archived_dir();
+        fs::create_dir_all(&archived_dir).wrap_err_with(|| {
+            format!(
+                "Could not create archive directory {}",
+                archived_dir.display()
+            )
+        })?;
+
+        // Keep both files if a task with the same file name was archived before
+        let file_path = &self.tasks[index].file_path;
+        let stem = file_path.file_stem().unwrap_or_default().to_string_lossy();
+        let mut archived_path = archived_dir.join(format!("{}.norg", stem));
+        let mut suffix = 2;
+        while archived_path.exists() {
+            archived_path = archived_dir.join(format!("{}_{}.norg", stem, suffix));
+            suffix += 1;
+        }
+
+        fs::rename(file_path, &archived_path)
+            .wrap_err_with(|| format!("Could not archive {}", file_path.display()))?;
+        self.written.push(file_path.clone());
+        self.written.push(archived_path);
+        self.tasks.remove(index);
+        Ok(())
+    }
+
+    /// Archives every completed task, returning how many were archived
+    pub fn archive_completed(&mut self) -> Result<usize> {
+        let completed: Vec<String> = self
+            .tasks
+            .iter()
+            .filter(|task| self.category_of(task) == KanbanCategory::Completed)
+            .map(|task| task.id.clone())
+            .collect();
+
+        for task_id in &completed {
+            self.archive_task(task_id)?;
+        }
+        Ok(completed.len())
+    }
+
+    pub fn data_dir(&self) -> &PathBuf {
+        &self.data_dir
+    }
+
+    /// Files that could not be parsed during the last load, with the reason
+    pub fn parse_errors(&self) -> &[(PathBuf, String)] {
+        &self.parse_errors
+    }
+
+    /// The files norgdo wrote, created or moved since the last call, so file watchers
+    /// can tell its own changes from outside ones
+    pub fn take_written_files(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.written)
+    }
+
+    /// Problems the last load worked around, such as duplicate task ids
+    pub fn load_warnings(&self) -> &[String] {
+        &self.load_warnings
+    }
+
+    /// Forgets the warnings shown for earlier loads, before reloading a batch of files
+    pub fn clear_load_warnings(&mut self) {
+        self.load_warnings.clear();
+    }
+
     pub fn get_tasks(&self) -> &[Task] {
         &self.tasks
     }
 
+    /// Serializes every task as a pretty-printed JSON array
+    pub fn export_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.tasks)?)
+    }
+
+    /// Finds the task a relation points to, by id or by (case-insensitive) title
+    pub fn resolve_task(&self, reference: &str) -> Option<&Task> {
+        self.tasks
+            .iter()
+            .find(|task| task.id == reference)
+            .or_else(|| {
+                self.tasks
+                    .iter()
+                    .find(|task| task.title.eq_ignore_ascii_case(reference))
+            })
+    }
+
+    /// Every relation of a task from its own point of view, including the ones other
+    /// tasks declare towards it. Each comes with the reference as written and the
+    /// task it resolves to, if any.
+    pub fn task_relations<'a>(
+        &'a self,
+        task: &'a Task,
+    ) -> Vec<(RelationType, &'a str, Option<&'a Task>)> {
+        let mut relations: Vec<_> = task
+            .relations
+            .iter()
+            .map(|relation| {
+                let target = relation.target_task_id.as_str();
+                (relation.relation_type, target, self.resolve_task(target))
+            })
+            .collect();
+
+        for other in self.tasks.iter().filter(|other| other.id != task.id) {
+            for relation in &other.relations {
+                let points_here = self
+                    .resolve_task(&relation.target_task_id)
+                    .is_some_and(|target| target.id == task.id);
+                let already_listed = relations
+                    .iter()
+                    .any(|(_, _, target)| target.is_some_and(|target| target.id == other.id));
+                if points_here && !already_listed {
+                    relations.push((
+                        relation.relation_type.inverse(),
+                        other.id.as_str(),
+                        Some(other),
+                    ));
+                }
+            }
+        }
+        relations
+    }
+
+    /// Relates a task to another one, referenced by id
+    pub fn add_relation(
+        &mut self,
+        task_id: &str,
+        target_task_id: &str,
+        relation_type: RelationType,
+    ) -> Result<()> {
+        self.load_full(task_id)?;
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id)
+            && !task
+                .relations
+                .iter()
+                .any(|relation| relation.target_task_id == target_task_id)
+        {
+            task.relations.push(TaskRelation {
+                target_task_id: target_task_id.to_string(),
+                relation_type,
+            });
+            self.task_changed(task_id)?;
+        }
+        Ok(())
+    }
+
     pub fn get_task_mut(&mut self, task_id: &str) -> Option<&mut Task> {
         self.tasks.iter_mut().find(|t| t.id == task_id)
     }
 
-    pub fn get_tasks_by_category(&self) -> HashMap<KanbanCategory, Vec<&Task>> {
+    pub fn get_tasks_by_category(
+        &self,
+        filter: &TaskFilter,
+    ) -> HashMap<KanbanCategory, Vec<&Task>> {
         let mut categorized = HashMap::new();
 
-        for task in &self.tasks {
-            let category = task.kanban_category();
+        for task in self.tasks.iter().filter(|task| filter.matches(task)) {
+            let category = self.category_of(task);
             categorized
                 .entry(category)
                 .or_insert_with(Vec::new)
                 .push(task);
         }
+        for tasks in categorized.values_mut() {
+            filter.sort(tasks);
+        }
 
         categorized
     }
 
-    pub fn search_tasks(&self, query: &str) -> Vec<&Task> {
-        let query_lower = query.to_lowercase();
+    /// Task counts per column, overall completion and todo counts per state, with the
+    /// same completion rules and columns as the board
+    pub fn stats(&self) -> Stats {
+        let by_category = self.get_tasks_by_category(&TaskFilter::default());
+        let columns = [KanbanCategory::YetToBeDone, KanbanCategory::InProgress]
+            .into_iter()
+            .chain(
+                self.columns
+                    .iter()
+                    .map(|column| KanbanCategory::Custom(column.name.clone())),
+            )
+            .chain([KanbanCategory::Completed])
+            .map(|category| {
+                let count = by_category.get(&category).map_or(0, Vec::len);
+                (category, count)
+            })
+            .collect();
+
+        let (completed_todos, counted_todos) = self
+            .tasks
+            .iter()
+            .map(|task| task.completion_counts(self.completion_rules))
+            .fold((0, 0), |(completed, total), (c, t)| {
+                (completed + c, total + t)
+            });
+
+        let mut todo_counts: HashMap<TodoState, usize> = HashMap::new();
+        for task in &self.tasks {
+            for (state, count) in task.todo_counts() {
+                *todo_counts.entry(state).or_insert(0) += count;
+            }
+        }
+
+        Stats {
+            total_tasks: self.tasks.len(),
+            columns,
+            completed_todos,
+            counted_todos,
+            todo_counts: TodoState::ALL
+                .into_iter()
+                .map(|state| {
+                    let count = todo_counts.get(&state).copied().unwrap_or(0);
+                    (state, count)
+                })
+                .collect(),
+        }
+    }
+
+    /// Every todo across all tasks as (task, todo index) pairs, in load order, optionally
+    /// only the ones in `state`
+    pub fn all_todos(&self, state: Option<&TodoState>) -> Vec<(&Task, usize)> {
         self.tasks
             .iter()
-            .filter(|task| {
-                task.title.to_lowercase().contains(&query_lower)
-                    || task.description.to_lowercase().contains(&query_lower)
-                    || task
-                        .todos
-                        .iter()
-                        .any(|todo| todo.text.to_lowercase().contains(&query_lower))
+            .flat_map(|task| {
+                task.todos
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, todo)| state.is_none_or(|state| todo.state == *state))
+                    .map(move |(index, _)| (task, index))
             })
             .collect()
     }
 
-    pub fn toggle_todo_state(&mut self, task_id: &str, todo_index: usize) -> Result<()> {
+    /// The project a task belongs to: the subdirectory of the data directory its file
+    /// lives in (e.g. `work/client`), or an empty string for top-level tasks
+    pub fn project_of(&self, task: &Task) -> String {
+        task.file_path
+            .parent()
+            .and_then(|dir| dir.strip_prefix(&self.data_dir).ok())
+            .map(|dir| {
+                dir.components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default()
+    }
+
+    /// Tasks grouped by project, sorted by project name so top-level tasks come first
+    pub fn get_tasks_by_project(&self, filter: &TaskFilter) -> Vec<(String, Vec<&Task>)> {
+        let mut projects: BTreeMap<String, Vec<&Task>> = BTreeMap::new();
+        for task in self.tasks.iter().filter(|task| filter.matches(task)) {
+            projects
+                .entry(self.project_of(task))
+                .or_default()
+                .push(task);
+        }
+        for tasks in projects.values_mut() {
+            filter.sort(tasks);
+        }
+        projects.into_iter().collect()
+    }
+
+    /// All tags used across tasks, sorted and without duplicates
+    pub fn all_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self
+            .tasks
+            .iter()
+            .flat_map(|task| task.tags.iter().map(String::as_str))
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Finds tasks matching a query, best matches first. Free text is matched fuzzily
+    /// (`rlse` finds "release") against the task's text, its tags (`#work`) and the
+    /// titles of related tasks, and `is:`, `state:`, `tag:` or `has:` tokens must all
+    /// match. An empty query returns every task.
+    pub fn search_tasks(&self, query: &str) -> Vec<(&Task, i64)> {
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let (tokens, text): (Vec<&str>, Vec<&str>) = query
+            .split_whitespace()
+            .partition(|term| Self::query_token(term).is_some());
+        let text = text.join(" ");
+        // Looked up once rather than per relation, relations by title use it as written
+        let titles: HashMap<&str, &str> = self
+            .tasks
+            .iter()
+            .map(|task| (task.id.as_str(), task.title.as_str()))
+            .collect();
+        // Tags are matched as `#tag`, built once per tag rather than per task
+        let hashtags: HashMap<&str, String> = self
+            .tasks
+            .iter()
+            .flat_map(|task| &task.tags)
+            .map(|tag| (tag.as_str(), format!("#{}", tag)))
+            .collect();
+
+        let mut results: Vec<(&Task, i64)> = self
+            .tasks
+            .iter()
+            .filter(|task| tokens.iter().all(|term| Self::term_matches(task, term)))
+            .filter_map(|task| {
+                if text.is_empty() {
+                    return Some((task, 0));
+                }
+                Self::fuzzy_score(&matcher, task, &text, &titles, &hashtags)
+                    .map(|score| (task, score))
+            })
+            .collect();
+
+        // Stable, so equally good matches keep their usual order
+        results.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        results
+    }
+
+    /// Best fuzzy score across a task's title, description, todos, tags and related
+    /// task titles. Title matches count double so they rank above matches buried in
+    /// the text.
+    fn fuzzy_score(
+        matcher: &SkimMatcherV2,
+        task: &Task,
+        text: &str,
+        titles: &HashMap<&str, &str>,
+        hashtags: &HashMap<&str, String>,
+    ) -> Option<i64> {
+        let title = matcher
+            .fuzzy_match(&task.title, text)
+            .map(|score| score * 2);
+        let description = matcher.fuzzy_match(&task.description, text);
+        let todos = task
+            .todos
+            .iter()
+            .map(|todo| matcher.fuzzy_match(&todo.text, text));
+        let tags = task
+            .tags
+            .iter()
+            .filter_map(|tag| hashtags.get(tag.as_str()))
+            .map(|hashtag| matcher.fuzzy_match(hashtag, text));
+        let related = task.relations.iter().map(|relation| {
+            let reference = relation.target_task_id.as_str();
+            matcher.fuzzy_match(titles.get(reference).unwrap_or(&reference), text)
+        });
+
+        [title, description]
+            .into_iter()
+            .chain(todos)
+            .chain(tags)
+            .chain(related)
+            .flatten()
+            .max()
+    }
+
+    /// Splits a structured term like `is:urgent` into its key and value
+    fn query_token(term: &str) -> Option<(&str, &str)> {
+        term.split_once(':')
+            .filter(|(key, _)| matches!(*key, "is" | "state" | "tag" | "has"))
+    }
+
+    /// Matches a single query term:
+    /// - `is:<state>` or `state:<state>`: has a todo in that state
+    /// - `tag:<tag>`: has that tag, case-insensitively
+    /// - `has:overdue`: the due date has passed with work left, `has:due`: has a due date
+    /// - anything else: free text in the title, description or todos
+    ///
+    /// Unknown states or `has:` values match nothing rather than silently everything.
+    fn term_matches(task: &Task, term: &str) -> bool {
+        match Self::query_token(term) {
+            Some(("is" | "state", state)) => TodoState::from_name(state)
+                .is_some_and(|state| task.todos.iter().any(|todo| todo.state == state)),
+            Some(("tag", tag)) => {
+                let tag = tag.trim_start_matches('#');
+                task.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+            }
+            Some(("has", property)) => match property.to_lowercase().as_str() {
+                "overdue" => task.is_overdue(chrono::Local::now().date_naive()),
+                "due" => task.due_date.is_some(),
+                _ => false,
+            },
+            _ => Self::task_matches_text(task, term),
+        }
+    }
+
+    /// Case-insensitive match against a task's title, description and todo texts
+    fn task_matches_text(task: &Task, query: &str) -> bool {
+        let query_lower = query.to_lowercase();
+        task.title.to_lowercase().contains(&query_lower)
+            || task.description.to_lowercase().contains(&query_lower)
+            || task
+                .todos
+                .iter()
+                .any(|todo| todo.text.to_lowercase().contains(&query_lower))
+    }
+
+    /// Cycles a todo through the common states. Like `set_todo_state`, this returns the
+    /// unfinished blockers when the change completes the task, so callers can warn.
+    pub fn toggle_todo_state(&mut self, task_id: &str, todo_index: usize) -> Result<Vec<&Task>> {
         use crate::task::TodoState;
 
+        self.load_full(task_id)?;
         if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            let was_completed =
+                task.kanban_category_with(self.completion_rules) == KanbanCategory::Completed;
             if let Some(todo) = task.todos.get_mut(todo_index) {
                 // Toggle between common states: Undone -> Pending -> Done -> Undone
                 todo.state = match todo.state {
@@ -180,39 +1153,493 @@ impl TaskManager {
                     TodoState::Recurring => TodoState::Done,
                 };
 
-                // Save the task file with updated TODO states
-                self.save_task(task_id)?;
+                return self.todo_state_changed(task_id, was_completed);
             }
         }
-        Ok(())
+        Ok(Vec::new())
     }
 
+    /// Changes the state of a todo. When that completes the task while tasks it depends
+    /// on are still unfinished, those are returned so the caller can warn about them.
     pub fn set_todo_state(
         &mut self,
         task_id: &str,
         todo_index: usize,
         new_state: TodoState,
-    ) -> Result<()> {
+    ) -> Result<Vec<&Task>> {
+        self.load_full(task_id)?;
         if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            let was_completed =
+                task.kanban_category_with(self.completion_rules) == KanbanCategory::Completed;
             if let Some(todo) = task.todos.get_mut(todo_index) {
                 todo.state = new_state;
-                // Save the task file with updated TODO state
-                self.save_task(task_id)?;
+                return self.todo_state_changed(task_id, was_completed);
             }
         }
+        Ok(Vec::new())
+    }
+
+    /// Marks every todo of a task done, returning their previous states so the change
+    /// can be undone with `restore_todo_states`. Like `set_todo_state`, it also returns
+    /// the unfinished blockers when this completes the task.
+    pub fn complete_all(&mut self, task_id: &str) -> Result<(Vec<TodoState>, Vec<&Task>)> {
+        self.load_full(task_id)?;
+        let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return Ok((Vec::new(), Vec::new()));
+        };
+
+        let was_completed =
+            task.kanban_category_with(self.completion_rules) == KanbanCategory::Completed;
+        let previous: Vec<TodoState> = task.todos.iter().map(|todo| todo.state.clone()).collect();
+        for todo in &mut task.todos {
+            todo.state = TodoState::Done;
+        }
+        let blockers = self.todo_state_changed(task_id, was_completed)?;
+        Ok((previous, blockers))
+    }
+
+    /// Puts back todo states saved by `complete_all`. Nothing happens if the number
+    /// of todos changed in the meantime.
+    pub fn restore_todo_states(&mut self, task_id: &str, states: Vec<TodoState>) -> Result<()> {
+        self.load_full(task_id)?;
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id)
+            && task.todos.len() == states.len()
+        {
+            for (todo, state) in task.todos.iter_mut().zip(states) {
+                todo.state = state;
+            }
+            self.task_changed(task_id)?;
+        }
         Ok(())
     }
 
+    /// Records a todo state change and looks for unfinished blockers if the task
+    /// just became completed
+    fn todo_state_changed(&mut self, task_id: &str, was_completed: bool) -> Result<Vec<&Task>> {
+        self.task_changed(task_id)?;
+
+        match self.tasks.iter().find(|t| t.id == task_id) {
+            Some(task) if !was_completed && self.category_of(task) == KanbanCategory::Completed => {
+                Ok(self.blocking_incomplete(task))
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Unfinished tasks this one depends on, directly or through other tasks it
+    /// depends on. Dependency cycles are only followed once.
+    pub fn blocking_incomplete<'a>(&'a self, task: &'a Task) -> Vec<&'a Task> {
+        let mut blockers = Vec::new();
+        let mut visited = HashSet::from([task.id.as_str()]);
+        let mut pending = vec![task];
+
+        while let Some(current) = pending.pop() {
+            for (relation_type, _, target) in self.task_relations(current) {
+                if let (RelationType::Requires, Some(target)) = (relation_type, target)
+                    && visited.insert(target.id.as_str())
+                {
+                    if self.category_of(target) != KanbanCategory::Completed {
+                        blockers.push(target);
+                    }
+                    pending.push(target);
+                }
+            }
+        }
+        blockers
+    }
+
+    /// Replaces the text of a todo, marking the task changed only if it differs
+    pub fn update_todo_text(
+        &mut self,
+        task_id: &str,
+        todo_index: usize,
+        text: String,
+    ) -> Result<()> {
+        self.load_full(task_id)?;
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id)
+            && let Some(todo) = task.todos.get_mut(todo_index)
+            && todo.text != text
+        {
+            todo.text = text;
+            self.task_changed(task_id)?;
+        }
+        Ok(())
+    }
+
+    /// Sets or, with `None`, removes the due date of a task
+    pub fn set_due_date(
+        &mut self,
+        task_id: &str,
+        due_date: Option<chrono::NaiveDate>,
+    ) -> Result<()> {
+        self.load_full(task_id)?;
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id)
+            && task.due_date != due_date
+        {
+            task.due_date = due_date;
+            self.task_changed(task_id)?;
+        }
+        Ok(())
+    }
+
+    /// Moves a todo (and its sub-items with it) `delta` levels deeper or shallower. A
+    /// todo can be at most one level deeper than the one above it, and never above level 1.
+    pub fn reindent_todo(&mut self, task_id: &str, todo_index: usize, delta: isize) -> Result<()> {
+        self.load_full(task_id)?;
+        let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return Ok(());
+        };
+        let Some(todo) = task.todos.get(todo_index) else {
+            return Ok(());
+        };
+
+        let level = todo.level;
+        let max_level = todo_index
+            .checked_sub(1)
+            .map_or(1, |previous| task.todos[previous].level + 1);
+        let new_level = level
+            .saturating_add_signed(delta)
+            .clamp(1, max_level.max(level));
+        if new_level == level {
+            return Ok(());
+        }
+
+        let end = task.todos[todo_index + 1..]
+            .iter()
+            .position(|child| child.level <= level)
+            .map_or(task.todos.len(), |offset| todo_index + 1 + offset);
+        for todo in &mut task.todos[todo_index..end] {
+            todo.level = todo.level + new_level - level;
+        }
+        self.task_changed(task_id)
+    }
+
+    /// Removes a todo and saves the task file. Nested children (the consecutive todos
+    /// that follow it at a deeper level) are removed along with it, mirroring how
+    /// deleting a list item and its sub-list would behave in the Norg file itself.
+    pub fn remove_todo(&mut self, task_id: &str, todo_index: usize) -> Result<()> {
+        self.load_full(task_id)?;
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id)
+            && let Some(todo) = task.todos.get(todo_index)
+        {
+            let level = todo.level;
+            let end = task.todos[todo_index + 1..]
+                .iter()
+                .position(|child| child.level <= level)
+                .map_or(task.todos.len(), |offset| todo_index + 1 + offset);
+
+            task.todos.drain(todo_index..end);
+            self.task_changed(task_id)?;
+        }
+        Ok(())
+    }
+
+    /// Picks a file name for a new task in `dir`. Different titles can sanitize to the
+    /// same name, so a numeric suffix (`Buy_milk_2.norg`) avoids overwriting another task.
+    fn new_file_path(&self, dir: &Path, title: &str) -> PathBuf {
+        let filename = Self::sanitize_filename(title);
+        let is_taken =
+            |path: &PathBuf| path.exists() || self.tasks.iter().any(|task| &task.file_path == path);
+
+        let mut file_path = dir.join(format!("{}.norg", filename));
+        let mut suffix = 2;
+        while is_taken(&file_path) {
+            file_path = dir.join(format!("{}_{}.norg", filename, suffix));
+            suffix += 1;
+        }
+        file_path
+    }
+
+    /// Copies of `todos` with every state back to undone
+    fn reset_todos(todos: &[TodoItem]) -> Vec<TodoItem> {
+        todos
+            .iter()
+            .map(|todo| TodoItem {
+                ordered: todo.ordered,
+                notes: todo.notes.clone(),
+                ..TodoItem::new(todo.text.clone(), TodoState::Undone, todo.level)
+            })
+            .collect()
+    }
+
+    /// Turns a title into a file name, keeping Unicode letters and digits (so "Café"
+    /// stays readable) and replacing whitespace and characters that are unsafe in paths.
+    /// Titles with nothing usable left, such as "???", fall back to `task`.
     fn sanitize_filename(title: &str) -> String {
-        title
+        let filename = title
             .chars()
             .map(|c| match c {
-                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
-                ' ' => '_',
-                _ => '_',
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                c if c.is_whitespace() || c.is_control() => '_',
+                c => c,
             })
             .collect::<String>()
-            .trim_matches('_')
-            .to_string()
+            // Leading dots would hide the file, trailing ones are stripped by Windows
+            .trim_matches(|c| c == '_' || c == '.')
+            .to_string();
+
+        if filename.is_empty() {
+            "task".to_string()
+        } else {
+            filename
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn manager(dir: &TempDir) -> TaskManager {
+        TaskManager::new(Some(dir.path().to_path_buf())).unwrap()
+    }
+
+    fn todo_texts(task: &Task) -> Vec<&str> {
+        task.todos.iter().map(|todo| todo.text.as_str()).collect()
+    }
+
+    #[test]
+    fn removing_a_todo_removes_its_children() {
+        let dir = TempDir::new();
+        dir.write(
+            "trip.norg",
+            "* Trip\n\n- ( ) Pack\n-- ( ) Socks\n--- ( ) Wool\n-- ( ) Shirts\n- ( ) Book hotel\n",
+        );
+        let mut manager = manager(&dir);
+        let task_id = manager.get_tasks()[0].id.clone();
+
+        // A nested todo only takes its own children along
+        manager.remove_todo(&task_id, 1).unwrap();
+        assert_eq!(
+            todo_texts(&manager.get_tasks()[0]),
+            ["Pack", "Shirts", "Book hotel"]
+        );
+
+        manager.remove_todo(&task_id, 0).unwrap();
+        assert_eq!(todo_texts(&manager.get_tasks()[0]), ["Book hotel"]);
+
+        // The removal was saved
+        let manager = self::manager(&dir);
+        assert_eq!(todo_texts(&manager.get_tasks()[0]), ["Book hotel"]);
+    }
+
+    #[test]
+    fn colliding_titles_get_their_own_files() {
+        let dir = TempDir::new();
+        let mut manager = manager(&dir);
+
+        let first = manager
+            .create_task("Buy milk?".to_string())
+            .unwrap()
+            .file_path
+            .clone();
+        let second = manager
+            .create_task("Buy milk*".to_string())
+            .unwrap()
+            .file_path
+            .clone();
+
+        assert_eq!(first, dir.path().join("Buy_milk.norg"));
+        assert_eq!(second, dir.path().join("Buy_milk_2.norg"));
+        assert!(first.exists() && second.exists());
+    }
+
+    #[test]
+    fn filenames_keep_unicode_letters() {
+        assert_eq!(TaskManager::sanitize_filename("Café résumé"), "Café_résumé");
+        assert_eq!(
+            TaskManager::sanitize_filename("買い物 リスト"),
+            "買い物_リスト"
+        );
+        assert_eq!(
+            TaskManager::sanitize_filename(" Ünïcödé: a/b "),
+            "Ünïcödé__a_b"
+        );
+    }
+
+    #[test]
+    fn symbols_only_titles_still_get_a_loadable_file() {
+        let dir = TempDir::new();
+        let mut manager = manager(&dir);
+
+        let path = manager
+            .create_task("???".to_string())
+            .unwrap()
+            .file_path
+            .clone();
+        assert_eq!(path, dir.path().join("task.norg"));
+
+        let manager = self::manager(&dir);
+        assert_eq!(manager.get_tasks().len(), 1);
+        assert_eq!(manager.get_tasks()[0].title, "???");
+    }
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| OsString::from(value))
+        }
+    }
+
+    #[test]
+    fn data_directory_precedence() {
+        let vars = [
+            ("NORGDO_DIR", "/tmp/norgdo-env"),
+            ("XDG_DATA_HOME", "/tmp/xdg"),
+        ];
+
+        let flag = TaskManager::get_data_directory(Some("/tmp/flag".into()), env(&vars));
+        assert_eq!(flag.unwrap(), PathBuf::from("/tmp/flag"));
+
+        let norgdo_dir = TaskManager::get_data_directory(None, env(&vars));
+        assert_eq!(norgdo_dir.unwrap(), PathBuf::from("/tmp/norgdo-env"));
+
+        // An empty variable counts as unset
+        let vars = [("NORGDO_DIR", ""), ("XDG_DATA_HOME", "/tmp/xdg")];
+        let data_dir = TaskManager::get_data_directory(None, env(&vars)).unwrap();
+        if cfg!(target_os = "linux") {
+            assert_eq!(data_dir, PathBuf::from("/tmp/xdg/norgdo"));
+        }
+    }
+
+    #[test]
+    fn relative_xdg_data_home_is_ignored() {
+        let vars = [("XDG_DATA_HOME", "relative/data")];
+        let data_dir = TaskManager::get_data_directory(None, env(&vars)).unwrap();
+        assert!(!data_dir.starts_with("relative"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_task_files_are_loaded_once() {
+        let dir = TempDir::new();
+        let target = dir.write("work/report.norg", "* Report\n\n- ( ) Draft\n");
+        std::os::unix::fs::symlink(&target, dir.path().join("report.norg")).unwrap();
+
+        let manager = manager(&dir);
+        let titles: Vec<&str> = manager
+            .get_tasks()
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Report"]);
+    }
+
+    fn search_titles<'a>(manager: &'a TaskManager, query: &str) -> Vec<&'a str> {
+        manager
+            .search_tasks(query)
+            .into_iter()
+            .map(|(task, _)| task.title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn search_matches_tags_and_related_titles() {
+        let dir = TempDir::new();
+        dir.write(
+            "report.norg",
+            "@document.meta\ntags: quarterly\n@end\n\n* Report\n\n- ( ) Draft\n",
+        );
+        dir.write(
+            "slides.norg",
+            "@relations\nblocked_by: Budget approval\n@end\n\n* Slides\n\n- ( ) Outline\n",
+        );
+        dir.write("budget.norg", "* Budget approval\n\n- ( ) Ask finance\n");
+        let manager = manager(&dir);
+
+        // Only the tag mentions it
+        assert_eq!(search_titles(&manager, "#quarterly"), ["Report"]);
+        // The blocker's title finds the blocked task too, ranked below the blocker
+        assert_eq!(
+            search_titles(&manager, "Budget approval"),
+            ["Budget approval", "Slides"]
+        );
+    }
+
+    #[test]
+    fn reloading_one_file_leaves_the_others_alone() {
+        let dir = TempDir::new();
+        let report = dir.write("report.norg", "* Report\n\n- ( ) Draft\n");
+        dir.write("slides.norg", "* Slides\n\n- ( ) Outline\n");
+        let mut manager = manager(&dir);
+        let report_id = Task::id_from_path(&report);
+        let titles = |manager: &TaskManager| -> Vec<String> {
+            manager
+                .get_tasks()
+                .iter()
+                .map(|t| t.title.clone())
+                .collect()
+        };
+        let before = titles(&manager);
+
+        std::fs::write(&report, "* Quarterly report\n\n- (x) Draft\n").unwrap();
+        // Two new files claiming the report's id, each reload adds its own warning
+        let content = format!("@document.meta\nid: {}\n@end\n\n* Copy\n", report_id);
+        let first = dir.write("copy.norg", &content);
+        let second = dir.write("copy_2.norg", &content);
+        manager.clear_load_warnings();
+        for path in [&report, &first, &second] {
+            manager.reload_task(path).unwrap();
+        }
+
+        let after = titles(&manager);
+        assert_eq!(after.len(), 4);
+        for (old, new) in before.iter().zip(&after) {
+            if old != "Report" {
+                assert_eq!(old, new);
+            }
+        }
+        assert!(after.contains(&"Quarterly report".to_string()));
+        assert_eq!(manager.load_warnings().len(), 2);
+    }
+
+    #[test]
+    fn padded_and_blank_todos_are_trimmed_or_dropped() {
+        let dir = TempDir::new();
+        let mut manager = manager(&dir);
+        let todos = ["  Buy milk  ", "   ", "\tCall mom"]
+            .map(|text| TodoItem::new(text.to_string(), TodoState::Undone, 1));
+
+        let task = manager
+            .create_task_with_details("Errands".to_string(), String::new(), None, todos.to_vec())
+            .unwrap();
+        assert_eq!(todo_texts(task), ["Buy milk", "Call mom"]);
+
+        let written = std::fs::read_to_string(&task.file_path).unwrap();
+        assert!(written.contains("- ( ) Buy milk\n- ( ) Call mom\n"));
+        assert!(written.lines().all(|line| line == line.trim_end()));
+    }
+
+    #[test]
+    fn completing_all_todos_warns_by_the_completion_rules() {
+        let dir = TempDir::new();
+        let launch = dir.write(
+            "launch.norg",
+            "@relations\nrequires: Budget\n@end\n\n* Launch\n\n- (_) Press release\n",
+        );
+        dir.write("budget.norg", "* Budget\n\n- ( ) Ask finance\n");
+        dir.write(
+            "empty.norg",
+            "@relations\nrequires: Budget\n@end\n\n* Empty\n",
+        );
+        let mut manager = manager(&dir);
+        manager.set_completion_rules(CompletionRules {
+            count_cancelled: false,
+            ..CompletionRules::default()
+        });
+
+        // Only a cancelled todo that doesn't count, so the task wasn't completed yet
+        let launch_id = Task::id_from_path(&launch);
+        let (previous, blockers) = manager.complete_all(&launch_id).unwrap();
+        assert_eq!(previous, [TodoState::Cancelled]);
+        let titles: Vec<&str> = blockers.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["Budget"]);
+
+        // Without todos there is nothing to complete
+        let empty_id = Task::id_from_path(&dir.path().join("empty.norg"));
+        let (_, blockers) = manager.complete_all(&empty_id).unwrap();
+        assert!(blockers.is_empty());
     }
 }
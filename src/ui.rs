@@ -1,16 +1,29 @@
-use crate::app::{App, AppMode, FocusedPane, WizardStep};
-use crate::task::{KanbanCategory, TodoState};
+use crate::app::{
+    App, AppMode, BoardMode, FocusedPane, ListLayout, Notification, PickerPurpose, Severity,
+    WizardStep,
+};
+use crate::config::{Config, Theme};
+use crate::help;
+use crate::input::TextInput;
+use norgdo::task::{
+    CompletionMode, CompletionRules, DueUrgency, KanbanCategory, RelationType, TodoState,
+};
+use norgdo::task_manager::{TaskFilter, TaskManager};
 use ratatui::widgets::BorderType;
 use ratatui::{
     prelude::*,
     widgets::{
-        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, Wrap,
+        Block, Borders, Clear, Gauge, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Wrap,
     },
 };
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
 
 pub fn render(app: &mut App, frame: &mut Frame) {
     match &app.mode.clone() {
-        AppMode::Dashboard => render_dashboard(app, frame),
+        AppMode::Dashboard | AppMode::Filter => render_dashboard(app, frame),
         AppMode::TaskDetail(task_id) => {
             let task_id = task_id.clone();
             render_task_detail(app, frame, &task_id);
@@ -19,8 +32,34 @@ pub fn render(app: &mut App, frame: &mut Frame) {
             let step = step.clone();
             render_task_wizard(app, frame, step);
         }
+        AppMode::CreateTask => {
+            render_dashboard(app, frame);
+            render_quick_add(app, frame, "Quick Add Task");
+        }
+        AppMode::TemplatePicker => {
+            render_dashboard(app, frame);
+            render_template_picker(app, frame);
+        }
+        AppMode::CreateFromTemplate(template) => {
+            let title = format!("New Task from '{}'", template);
+            render_dashboard(app, frame);
+            render_quick_add(app, frame, &title);
+        }
         AppMode::Search => render_search(app, frame),
         AppMode::Help => render_help(app, frame),
+        AppMode::ParseErrors => {
+            render_dashboard(app, frame);
+            render_parse_errors(app, frame);
+        }
+        AppMode::ConfirmQuit => {
+            render_dashboard(app, frame);
+            render_confirm_quit(app, frame);
+        }
+        AppMode::ConfirmDiscardWizard(step) => {
+            let step = step.clone();
+            render_task_wizard(app, frame, step);
+            render_confirm_discard_wizard(app, frame);
+        }
         AppMode::TodoStateSelect {
             task_id,
             todo_index,
@@ -29,85 +68,193 @@ pub fn render(app: &mut App, frame: &mut Frame) {
             let todo_index = *todo_index;
             render_todo_state_select(app, frame, &task_id, todo_index);
         }
+        AppMode::EditTodo {
+            task_id,
+            todo_index,
+        } => {
+            let task_id = task_id.clone();
+            let todo_index = *todo_index;
+            render_task_detail(app, frame, &task_id);
+            render_todo_edit(app, frame, &task_id, todo_index);
+        }
+        AppMode::EditDueDate(task_id) => {
+            let task_id = task_id.clone();
+            render_task_detail(app, frame, &task_id);
+            render_due_date_edit(app, frame);
+        }
+        AppMode::AllTodos => render_all_todos(app, frame),
+        AppMode::TaskPicker {
+            purpose,
+            query,
+            selected,
+        } => {
+            match app.picker_return_mode.clone() {
+                AppMode::TaskDetail(task_id) => render_task_detail(app, frame, &task_id),
+                _ => render_dashboard(app, frame),
+            }
+            render_task_picker(app, frame, *purpose, query, *selected);
+        }
     }
 
-    // Render error message if present
-    if let Some(error) = &app.error_message {
-        render_error_popup(frame, error);
+    if let Some(started) = app.loading_since() {
+        render_loading(frame, &app.config.theme, started.elapsed());
+    }
+
+    if !app.notifications.is_empty() {
+        render_notifications(frame, &app.notifications, &app.config.theme);
     }
 }
 
 fn render_dashboard(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Title
             Constraint::Min(0),    // Main content
             Constraint::Length(3), // Help
+            Constraint::Length(1), // Status bar
         ])
         .split(frame.area());
 
     // Title
-    let title = Paragraph::new("NorgDo - Terminal Task Manager")
+    let mut title_text = "NorgDo - Terminal Task Manager".to_string();
+    if let Some(tag) = &app.filter.tag {
+        title_text.push_str(&format!(" [tag: #{}]", tag));
+    }
+    if !app.filter.query.trim().is_empty() {
+        title_text.push_str(&format!(" [filter: {}]", app.filter.query.trim()));
+    }
+    if let Some(agenda) = &app.filter.agenda {
+        title_text.push_str(&format!(
+            " [agenda: next {} day{}{}]",
+            agenda.days,
+            if agenda.days == 1 { "" } else { "s" },
+            if agenda.include_overdue {
+                " + overdue"
+            } else {
+                ""
+            }
+        ));
+    }
+    let mut title_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    // An invalid format fails to write, in which case the clock is left out
+    let mut clock = String::new();
+    let now = chrono::Local::now();
+    if !app.config.clock_format.is_empty()
+        && write!(clock, " {} ", now.format(&app.config.clock_format)).is_ok()
+    {
+        title_block = title_block.title(Line::from(clock).style(Style::default().fg(theme.muted)));
+    }
+    let parse_error_count = app.task_manager.parse_errors().len();
+    if parse_error_count > 0 {
+        title_block = title_block.title(
+            Line::from(format!(
+                " ⚠ {} file{} failed to parse (!) ",
+                parse_error_count,
+                if parse_error_count == 1 { "" } else { "s" }
+            ))
+            .style(Style::default().fg(theme.error))
+            .right_aligned(),
+        );
+    }
+    let title = Paragraph::new(title_text)
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        );
+        .block(title_block);
     frame.render_widget(title, chunks[0]);
 
-    // Main kanban board
-    let kanban_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-        ])
-        .split(chunks[1]);
+    // Main kanban board, or a getting started hint while there are no tasks at all
+    if app.task_manager.get_tasks().is_empty() {
+        app.column_layouts.clear();
+        render_empty_dashboard(frame, chunks[1], theme, app.task_manager.data_dir());
+    } else if app.board_mode == BoardMode::Project {
+        app.column_layouts.clear();
+        render_project_board(
+            &app.task_manager,
+            &app.filter,
+            theme,
+            &mut app.project_list_state,
+            frame,
+            chunks[1],
+        );
+    } else {
+        // Get tasks and focused pane before rendering columns
+        let tasks_by_category = app.task_manager.get_tasks_by_category(&app.filter);
+        let focused_pane = app.focused_pane.clone();
+
+        let panes: Vec<(KanbanCategory, FocusedPane)> = app
+            .board_panes()
+            .into_iter()
+            .map(|pane| (pane.category(), pane))
+            .collect();
 
-    // Get tasks and focused pane before rendering columns
-    let tasks_by_category = app.task_manager.get_tasks_by_category();
-    let focused_pane = app.focused_pane.clone();
+        // Hidden columns share out their space, so two columns are 50/50
+        let kanban_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, panes.len() as u32); panes.len()])
+            .split(chunks[1]);
 
-    // Render kanban columns one at a time to avoid borrowing conflicts
-    render_single_kanban_column(
-        &mut app.list_states,
-        frame,
-        kanban_chunks[0],
-        KanbanCategory::YetToBeDone,
-        &tasks_by_category,
-        focused_pane == FocusedPane::YetToBeDone,
-    );
+        // A hidden column must not take clicks, so only the drawn ones are recorded
+        app.column_layouts.clear();
 
-    render_single_kanban_column(
-        &mut app.list_states,
-        frame,
-        kanban_chunks[1],
-        KanbanCategory::InProgress,
-        &tasks_by_category,
-        focused_pane == FocusedPane::InProgress,
-    );
+        // Render kanban columns one at a time to avoid borrowing conflicts
+        let columns = kanban_chunks
+            .iter()
+            .zip(panes)
+            .map(|(area, (category, pane))| (*area, category, pane));
+        for (area, category, pane) in columns {
+            let layout = render_single_kanban_column(
+                &mut app.list_states,
+                &app.config,
+                frame,
+                area,
+                category.clone(),
+                &tasks_by_category,
+                focused_pane == pane,
+            );
+            let offset = app.list_states[&category].offset();
+            render_scrollbar(
+                frame,
+                area,
+                app.scrollbar_states.entry(category.clone()).or_default(),
+                layout.item_heights.len(),
+                layout.visible_items(offset),
+                offset,
+            );
+            app.column_layouts.insert(category, layout);
+        }
+    }
 
-    render_single_kanban_column(
-        &mut app.list_states,
-        frame,
-        kanban_chunks[2],
-        KanbanCategory::Completed,
-        &tasks_by_category,
-        focused_pane == FocusedPane::Completed,
-    );
+    render_status_bar(app, frame, chunks[3]);
+
+    // Filter bar, shown in place of the help text while editing the filter
+    if app.mode == AppMode::Filter {
+        render_text_input(
+            frame,
+            chunks[2],
+            &app.filter_input,
+            Vec::new(),
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.focused_border))
+                .title(
+                    "Filter (is:<state> tag:<tag> has:overdue text) | Enter: Apply | Esc: Clear",
+                ),
+        );
+        return;
+    }
 
     // Help text
-    let help_text = "Press ? for help | Press Esc/q to quit";
-    let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::Gray))
+    let help = Paragraph::new(app.mode_hint())
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
         .block(
@@ -119,122 +266,597 @@ fn render_dashboard(app: &mut App, frame: &mut Frame) {
     frame.render_widget(help, chunks[2]);
 }
 
+/// One line with the task counts per column and the data directory in use
+fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.config.theme;
+    let tasks = app.task_manager.get_tasks();
+    let count = |category: KanbanCategory| {
+        tasks
+            .iter()
+            .filter(|task| app.task_manager.category_of(task) == category)
+            .count()
+    };
+
+    let mut status = format!(" {} tasks", tasks.len());
+    let categories = [KanbanCategory::YetToBeDone, KanbanCategory::InProgress]
+        .into_iter()
+        .chain(
+            app.task_manager
+                .columns()
+                .iter()
+                .map(|column| KanbanCategory::Custom(column.name.clone())),
+        )
+        .chain(std::iter::once(KanbanCategory::Completed));
+    for category in categories {
+        status.push_str(&format!(
+            " | {}: {}",
+            category.to_string(),
+            count(category.clone())
+        ));
+    }
+    if app.filter.is_active() {
+        let shown = tasks.iter().filter(|task| app.filter.matches(task)).count();
+        status.push_str(&format!(" | {} shown", shown));
+    }
+
+    // Abbreviate the home directory like a shell prompt would
+    let data_dir = app.task_manager.data_dir();
+    let data_dir = dirs::home_dir()
+        .and_then(|home| data_dir.strip_prefix(home).ok())
+        .map_or_else(
+            || data_dir.display().to_string(),
+            |relative| Path::new("~").join(relative).display().to_string(),
+        );
+    let data_dir = format!("{} ", data_dir);
+
+    let status_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(data_dir.chars().count() as u16),
+        ])
+        .split(area);
+    let style = Style::default().fg(theme.muted);
+    frame.render_widget(Paragraph::new(status).style(style), status_chunks[0]);
+    frame.render_widget(
+        Paragraph::new(data_dir)
+            .style(style)
+            .alignment(Alignment::Right),
+        status_chunks[1],
+    );
+}
+
+fn render_empty_dashboard(frame: &mut Frame, area: Rect, theme: &Theme, data_dir: &Path) {
+    let lines = vec![
+        Line::from(Span::styled(
+            "No tasks yet",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Press ", Style::default().fg(theme.text)),
+            Span::styled(
+                "n",
+                Style::default()
+                    .fg(theme.focused_border)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " to create your first task",
+                Style::default().fg(theme.text),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Tasks are stored in {}", data_dir.display()),
+            Style::default().fg(theme.muted),
+        )),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Center the message vertically inside the board
+    let top_padding = inner.height.saturating_sub(lines.len() as u16) / 2;
+    let message_area = Rect {
+        y: inner.y + top_padding,
+        height: inner.height - top_padding,
+        ..inner
+    };
+    let message = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(message, message_area);
+}
+
+/// One swimlane per project: a header with the project's status breakdown followed by
+/// its tasks, all in one scrollable list
+fn render_project_board(
+    task_manager: &TaskManager,
+    filter: &TaskFilter,
+    theme: &Theme,
+    list_state: &mut ratatui::widgets::ListState,
+    frame: &mut Frame,
+    area: Rect,
+) {
+    let mut items = Vec::new();
+
+    for (project, tasks) in task_manager.get_tasks_by_project(filter) {
+        let count = |category: KanbanCategory| {
+            tasks
+                .iter()
+                .filter(|task| task_manager.category_of(task) == category)
+                .count()
+        };
+        let name = if project.is_empty() {
+            "No project".to_string()
+        } else {
+            project
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(
+                format!("{} ({})", name, tasks.len()),
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  {} to do · {} in progress{} · {} completed",
+                    count(KanbanCategory::YetToBeDone),
+                    count(KanbanCategory::InProgress),
+                    task_manager
+                        .columns()
+                        .iter()
+                        .map(|column| format!(
+                            " · {} {}",
+                            count(KanbanCategory::Custom(column.name.clone())),
+                            column.name.to_lowercase()
+                        ))
+                        .collect::<String>(),
+                    count(KanbanCategory::Completed),
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ])));
+
+        for task in tasks {
+            let category = task_manager.category_of(task);
+            let status_color = match category {
+                KanbanCategory::YetToBeDone => theme.undone,
+                KanbanCategory::InProgress => theme.pending,
+                KanbanCategory::Completed => theme.done,
+                KanbanCategory::Custom(_) => theme.urgent,
+            };
+            items.push(ListItem::new(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("{:<15}", category.to_string()),
+                    Style::default().fg(status_color),
+                ),
+                Span::styled(task.title.as_str(), Style::default().fg(theme.text)),
+                Span::styled(
+                    format!(
+                        " {:.0}%",
+                        task.completion_percentage_with(task_manager.completion_rules())
+                    ),
+                    Style::default().fg(theme.muted),
+                ),
+            ])));
+        }
+    }
+
+    let row_count = items.len();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.focused_border))
+        .title("Projects");
+    let visible_rows = block.inner(area).height as usize;
+    let list = List::new(items)
+        .block(block)
+        .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
+        .highlight_style(Style::default().bg(theme.selection));
+    frame.render_stateful_widget(list, area, list_state);
+    render_scrollbar(
+        frame,
+        area,
+        &mut ScrollbarState::default(),
+        row_count,
+        visible_rows,
+        list_state.offset(),
+    );
+}
+
+/// Renders one kanban column and returns its layout for mouse handling
 fn render_single_kanban_column(
     list_states: &mut std::collections::HashMap<KanbanCategory, ratatui::widgets::ListState>,
+    config: &Config,
     frame: &mut Frame,
     area: Rect,
     category: KanbanCategory,
-    tasks_by_category: &std::collections::HashMap<KanbanCategory, Vec<&crate::task::Task>>,
+    tasks_by_category: &std::collections::HashMap<KanbanCategory, Vec<&norgdo::task::Task>>,
     is_focused: bool,
-) {
+) -> ListLayout {
+    let theme = &config.theme;
     let empty_vec = vec![];
     let tasks = tasks_by_category.get(&category).unwrap_or(&empty_vec);
+    let today = chrono::Local::now().date_naive();
 
     let items: Vec<ListItem> = tasks
         .iter()
         .map(|task| {
-            let completion = task.completion_percentage();
-            let todo_counts = task.todo_counts();
-            let total_todos = task.todos.len();
-
-            let title_line = Line::from(vec![Span::styled(
-                &task.title,
-                Style::default().add_modifier(Modifier::BOLD),
-            )]);
-
-            let progress_line = if total_todos > 0 {
-                // Create visual progress bar with block characters
-                let bar_width = 20; // Total width of progress bar
-                let filled_width = ((completion / 100.0) * bar_width as f64) as usize;
-                let empty_width = bar_width - filled_width;
-
-                let progress_bar =
-                    format!("{}{}", "█".repeat(filled_width), "░".repeat(empty_width));
-
-                Line::from(vec![Span::styled(
-                    format!(
-                        "{} {:.0}% ({}/{})",
-                        progress_bar,
-                        completion,
-                        todo_counts.get(&TodoState::Done).unwrap_or(&0),
-                        total_todos
-                    ),
-                    Style::default().fg(Color::Gray),
-                )])
+            let title_style = if task.has_urgent() {
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD)
             } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            let overdue_marker = task.is_overdue(today).then(|| {
+                Span::styled(
+                    "⚠ ",
+                    Style::default()
+                        .fg(theme.urgent)
+                        .add_modifier(Modifier::BOLD),
+                )
+            });
+            let due_badge = task
+                .due_urgency(today)
+                .zip(task.due_date)
+                .map(|(urgency, due)| {
+                    let (text, style) = match urgency {
+                        DueUrgency::Overdue => (
+                            format!("due {}", due.format("%Y-%m-%d")),
+                            Style::default()
+                                .fg(theme.error)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        DueUrgency::Today => (
+                            "due today".to_string(),
+                            Style::default()
+                                .fg(theme.urgent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        DueUrgency::ThisWeek => (
+                            format!("due {}", due.format("%a")),
+                            Style::default().fg(theme.text),
+                        ),
+                        DueUrgency::Later => (
+                            format!("due {}", due.format("%Y-%m-%d")),
+                            Style::default().fg(theme.muted),
+                        ),
+                    };
+                    [Span::raw(" "), Span::styled(format!("[{}]", text), style)]
+                });
+            let title_line = Line::from(
+                overdue_marker
+                    .into_iter()
+                    .chain(std::iter::once(Span::styled(&task.title, title_style)))
+                    .chain(due_badge.into_iter().flatten())
+                    .collect::<Vec<_>>(),
+            );
+
+            let tag_line = (!task.tags.is_empty()).then(|| {
+                Line::from(
+                    task.tags
+                        .iter()
+                        .flat_map(|tag| {
+                            [
+                                Span::styled(
+                                    format!(" #{} ", tag),
+                                    Style::default().fg(Color::Black).bg(tag_color(tag)),
+                                ),
+                                Span::raw(" "),
+                            ]
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            });
+
+            // Compact todo count per state, with the icons and colors of the detail view
+            let breakdown_line = (!task.todos.is_empty()).then(|| {
+                let counts = task.todo_counts();
+                Line::from(
+                    TodoState::ALL
+                        .iter()
+                        .filter_map(|state| Some((state, *counts.get(state)?)))
+                        .flat_map(|(state, count)| {
+                            [
+                                Span::styled(
+                                    format!("{}{}", count, todo_state_symbol(state)),
+                                    Style::default().fg(theme.todo_state(state)),
+                                ),
+                                Span::raw(" "),
+                            ]
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            });
+
+            // The progress gauge is drawn over this line once the list is rendered
+            let progress_line = if task.todos.is_empty() {
                 Line::from(vec![Span::styled(
                     "No todos",
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme.muted),
                 )])
+            } else {
+                Line::from("")
             };
 
-            ListItem::new(vec![title_line, progress_line]).style(Style::default().fg(Color::White))
+            let lines = std::iter::once(title_line)
+                .chain(tag_line)
+                .chain(breakdown_line)
+                .chain(std::iter::once(progress_line))
+                .collect::<Vec<_>>();
+
+            ListItem::new(lines).style(Style::default().fg(theme.text))
         })
         .collect();
 
     let border_style = if is_focused {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.focused_border)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.border)
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(border_style)
+        .title(format!("{} ({})", category.to_string(), tasks.len()));
+    let layout = ListLayout {
+        area: block.inner(area),
+        item_heights: items.iter().map(|item| item.height() as u16).collect(),
     };
 
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(border_style)
-                .title(format!("{} ({})", category.to_string(), tasks.len())),
-        )
+        .block(block)
         .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
-        .highlight_style(Style::default().bg(Color::Black))
+        .highlight_style(Style::default().bg(theme.selection))
         .highlight_symbol("» ");
 
     let state = list_states.get_mut(&category).unwrap();
     frame.render_stateful_widget(list, area, state);
+    render_progress_gauges(
+        frame,
+        theme,
+        tasks,
+        &layout,
+        state,
+        config.completion_rules(),
+    );
+    layout
+}
+
+/// Draws a completion gauge on the last line of every visible card with todos
+fn render_progress_gauges(
+    frame: &mut Frame,
+    theme: &Theme,
+    tasks: &[&norgdo::task::Task],
+    layout: &ListLayout,
+    state: &ratatui::widgets::ListState,
+    completion_rules: CompletionRules,
+) {
+    let bottom = layout.area.bottom();
+    let mut top = layout.area.y;
+
+    for (index, task) in tasks.iter().enumerate().skip(state.offset()) {
+        let height = layout.item_heights[index];
+        if top + height > bottom {
+            break;
+        }
+
+        if !task.todos.is_empty() {
+            let completion = task.completion_percentage_with(completion_rules);
+            let (done, total) = match completion_rules.mode {
+                // Cancelled todos count toward completion but are not listed as done
+                CompletionMode::Flat => (
+                    task.todo_counts()
+                        .get(&TodoState::Done)
+                        .copied()
+                        .unwrap_or(0),
+                    task.completion_counts(completion_rules).1,
+                ),
+                CompletionMode::Rollup => task.completion_counts(completion_rules),
+            };
+            let color = if completion < 34.0 {
+                theme.error
+            } else if completion < 67.0 {
+                theme.urgent
+            } else {
+                theme.done
+            };
+            let background = if state.selected() == Some(index) {
+                theme.selection
+            } else {
+                Color::Reset
+            };
+
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(color).bg(background))
+                .ratio((completion / 100.0).clamp(0.0, 1.0))
+                .label(format!("{:.0}% ({}/{})", completion, done, total))
+                .use_unicode(true);
+            let gauge_area = Rect {
+                y: top + height - 1,
+                height: 1,
+                ..layout.area
+            };
+            frame.render_widget(gauge, gauge_area);
+        }
+
+        top += height;
+    }
+}
+
+/// Picks a stable chip color for a tag so the same tag always looks the same
+fn tag_color(tag: &str) -> Color {
+    const PALETTE: [Color; 6] = [
+        Color::LightBlue,
+        Color::LightGreen,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::LightYellow,
+        Color::LightRed,
+    ];
+    let hash = tag.bytes().fold(0usize, |acc, b| {
+        acc.wrapping_mul(31).wrapping_add(b as usize)
+    });
+    PALETTE[hash % PALETTE.len()]
+}
+
+/// Nerd font icon for a todo state
+fn todo_state_symbol(state: &TodoState) -> &'static str {
+    match state {
+        TodoState::Done => "",
+        TodoState::Cancelled => "",
+        TodoState::Pending => "",
+        TodoState::Urgent => "",
+        TodoState::OnHold => "",
+        TodoState::Uncertain => "",
+        TodoState::Recurring => "",
+        TodoState::Undone => "",
+    }
+}
+
+/// Renders a single-line text input after an optional styled prefix, scrolling it
+/// horizontally so the cursor stays visible, and places the terminal cursor
+fn render_text_input(
+    frame: &mut Frame,
+    area: Rect,
+    input: &TextInput,
+    prefix: Vec<Span>,
+    block: Block,
+) {
+    let inner = block.inner(area);
+    let prefix_width: usize = prefix.iter().map(Span::width).sum();
+    let cursor_column = (prefix_width + Span::raw(input.before_cursor()).width()) as u16;
+    let scroll = cursor_column.saturating_sub(inner.width.saturating_sub(1));
+
+    let mut spans = prefix;
+    spans.push(Span::raw(input.value()));
+    let paragraph = Paragraph::new(Line::from(spans))
+        .scroll((0, scroll))
+        .block(block);
+    frame.render_widget(paragraph, area);
+    frame.set_cursor_position((inner.x + cursor_column - scroll, inner.y));
 }
 
 fn render_task_detail(app: &mut App, frame: &mut Frame, task_id: &str) {
+    let theme = &app.config.theme;
     if let Some(task) = app
         .task_manager
         .get_tasks()
         .iter()
         .find(|t| t.id == task_id)
     {
+        let description = if task.description.is_empty() {
+            "No description provided.".to_string()
+        } else {
+            task.description.clone()
+        };
+        let description = Paragraph::new(description).wrap(Wrap { trim: true });
+
+        // Grow the description with its content, up to a third of the screen
+        let description_lines = description.line_count(frame.area().width.saturating_sub(2)) as u16;
+        let description_height = (description_lines + 2).clamp(3, (frame.area().height / 3).max(3));
+
+        let relation_lines = relation_lines(&app.task_manager, theme, task);
+        let relations_height = if relation_lines.is_empty() {
+            0
+        } else {
+            relation_lines.len() as u16 + 2
+        };
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Title
-                Constraint::Length(5), // Description
-                Constraint::Min(0),    // Todos
-                Constraint::Length(3), // Help
+                Constraint::Length(3),                  // Title
+                Constraint::Length(description_height), // Description
+                Constraint::Length(relations_height),   // Relations
+                Constraint::Min(0),                     // Todos
+                Constraint::Length(3),                  // Help
             ])
             .split(frame.area());
 
         // Title
+        let is_dirty = app.task_manager.is_dirty(&task.id);
         let title = Paragraph::new(task.title.clone())
             .wrap(Wrap { trim: true })
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .title("Task"),
+                    .title(if is_dirty {
+                        Line::from(vec![
+                            Span::raw("Task "),
+                            Span::styled("[unsaved]", Style::default().fg(theme.focused_border)),
+                        ])
+                    } else {
+                        Line::from("Task")
+                    })
+                    .title(
+                        Line::from(format!(
+                            " updated {} ",
+                            relative_time(task.updated_at, chrono::Utc::now())
+                        ))
+                        .style(Style::default().fg(theme.muted))
+                        .right_aligned(),
+                    )
+                    .title_bottom(
+                        Line::from(
+                            task.due_date
+                                .map(|due| format!(" due {} ", due.format("%Y-%m-%d")))
+                                .unwrap_or_default(),
+                        )
+                        .style(Style::default().fg(theme.muted))
+                        .right_aligned(),
+                    ),
             );
         frame.render_widget(title, chunks[0]);
 
-        // Description
-        let description = if task.description.is_empty() {
-            "No description provided.".to_string()
-        } else {
-            task.description.clone()
-        };
-        let desc_widget = Paragraph::new(description).wrap(Wrap { trim: true }).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .title("Description"),
+        // Description, scrollable when it doesn't fit
+        let description_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Description");
+        app.description_area = chunks[1];
+        let visible_lines = description_block.inner(chunks[1]).height;
+        app.description_scroll = app
+            .description_scroll
+            .min(description_lines.saturating_sub(visible_lines));
+        frame.render_widget(
+            description
+                .block(description_block)
+                .scroll((app.description_scroll, 0)),
+            chunks[1],
         );
-        frame.render_widget(desc_widget, chunks[1]);
+        render_scrollbar(
+            frame,
+            chunks[1],
+            &mut ScrollbarState::default(),
+            description_lines as usize,
+            visible_lines as usize,
+            app.description_scroll as usize,
+        );
+
+        // Relations
+        if !relation_lines.is_empty() {
+            let relations = Paragraph::new(relation_lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title("Relations"),
+            );
+            frame.render_widget(relations, chunks[2]);
+        }
 
         // Todos
         let todo_items: Vec<ListItem> = task
@@ -242,27 +864,8 @@ fn render_task_detail(app: &mut App, frame: &mut Frame, task_id: &str) {
             .iter()
             .map(|todo| {
                 let indent = "  ".repeat(todo.level);
-                let state_symbol = match todo.state {
-                    TodoState::Done => "",
-                    TodoState::Cancelled => "",
-                    TodoState::Pending => "",
-                    TodoState::Urgent => "",
-                    TodoState::OnHold => "",
-                    TodoState::Uncertain => "",
-                    TodoState::Recurring => "",
-                    TodoState::Undone => "",
-                };
-
-                let color = match todo.state {
-                    TodoState::Done => Color::Green,
-                    TodoState::Cancelled => Color::Red,
-                    TodoState::Urgent => Color::Yellow,
-                    TodoState::Pending => Color::Blue,
-                    TodoState::Uncertain => Color::Magenta,
-                    TodoState::OnHold => Color::Cyan,
-                    TodoState::Recurring => Color::LightYellow,
-                    TodoState::Undone => Color::White,
-                };
+                let state_symbol = todo_state_symbol(&todo.state);
+                let color = theme.todo_state(&todo.state);
 
                 ListItem::new(Line::from(vec![
                     Span::raw(indent),
@@ -270,28 +873,47 @@ fn render_task_detail(app: &mut App, frame: &mut Frame, task_id: &str) {
                     Span::raw(" "),
                     Span::styled(&todo.text, Style::default().fg(color)),
                     Span::styled(
-                        format!(" ({})", todo.state.to_string()),
-                        Style::default().fg(Color::Gray),
+                        format!(" ({})", todo.state_label()),
+                        Style::default().fg(theme.muted),
                     ),
                 ]))
             })
             .collect();
 
+        let todos_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(format!("Todo Items ({})", task.todos.len()));
+        app.todo_list_layout = ListLayout {
+            area: todos_block.inner(chunks[3]),
+            item_heights: todo_items.iter().map(|item| item.height() as u16).collect(),
+        };
+
         let todos_list = List::new(todo_items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .title(format!("Todo Items ({})", task.todos.len())),
-            )
+            .block(todos_block)
             .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
-            .highlight_style(Style::default().bg(Color::Black))
+            .highlight_style(Style::default().bg(theme.selection))
             .highlight_symbol("» ");
-        frame.render_stateful_widget(todos_list, chunks[2], &mut app.todo_list_state);
+        frame.render_stateful_widget(todos_list, chunks[3], &mut app.todo_list_state);
+        render_scrollbar(
+            frame,
+            chunks[3],
+            &mut ScrollbarState::default(),
+            task.todos.len(),
+            app.todo_list_layout.area.height as usize,
+            app.todo_list_state.offset(),
+        );
 
         // Help
-        let help = Paragraph::new("Press ? for help | Press Esc/q to quit")
-            .style(Style::default().fg(Color::Gray))
+        let help_text = match app.mode {
+            AppMode::EditTodo { .. } => "Enter: Save TODO | Esc: Cancel".to_string(),
+            AppMode::EditDueDate(_) => {
+                "Enter: Set due date (empty removes it) | Esc: Cancel".to_string()
+            }
+            _ => app.mode_hint(),
+        };
+        let help = Paragraph::new(help_text)
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
             .block(
@@ -300,11 +922,244 @@ fn render_task_detail(app: &mut App, frame: &mut Frame, task_id: &str) {
                     .border_type(BorderType::Rounded)
                     .title("Help"),
             );
-        frame.render_widget(help, chunks[3]);
+        frame.render_widget(help, chunks[4]);
+    }
+}
+
+/// Every todo across all tasks, each followed by the title of its task
+fn render_all_todos(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(frame.area());
+
+    let todos = app
+        .task_manager
+        .all_todos(app.all_todos_state_filter.as_ref());
+    let items: Vec<ListItem> = todos
+        .iter()
+        .map(|(task, index)| {
+            let todo = &task.todos[*index];
+            let color = theme.todo_state(&todo.state);
+            ListItem::new(Line::from(vec![
+                Span::raw("  ".repeat(todo.level.saturating_sub(1))),
+                Span::styled(todo_state_symbol(&todo.state), Style::default().fg(color)),
+                Span::raw(" "),
+                Span::styled(&todo.text, Style::default().fg(color)),
+                Span::styled(
+                    format!("  · {}", task.title),
+                    Style::default().fg(theme.muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    let title = match &app.all_todos_state_filter {
+        Some(state) => format!("All TODOs ({}) [state: {}]", todos.len(), state.to_string()),
+        None => format!("All TODOs ({})", todos.len()),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.focused_border))
+        .title(title);
+    let visible_rows = block.inner(chunks[0]).height as usize;
+    let row_count = items.len();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
+        .highlight_style(Style::default().bg(theme.selection));
+    frame.render_stateful_widget(list, chunks[0], &mut app.all_todos_list_state);
+    render_scrollbar(
+        frame,
+        chunks[0],
+        &mut ScrollbarState::default(),
+        row_count,
+        visible_rows,
+        app.all_todos_list_state.offset(),
+    );
+
+    let help = Paragraph::new(app.mode_hint())
+        .style(Style::default().fg(theme.muted))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Help"),
+        );
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Draws the todo being edited over its row of the todo list
+fn render_todo_edit(app: &mut App, frame: &mut Frame, task_id: &str, todo_index: usize) {
+    let theme = &app.config.theme;
+    let Some(todo) = app
+        .task_manager
+        .get_tasks()
+        .iter()
+        .find(|t| t.id == task_id)
+        .and_then(|task| task.todos.get(todo_index))
+    else {
+        return;
+    };
+
+    // The list keeps the todo in view since it is the selected one
+    let layout = &app.todo_list_layout;
+    let offset = app.todo_list_state.offset();
+    let row_offset: u16 = layout.item_heights[offset..todo_index.max(offset)]
+        .iter()
+        .sum();
+    if row_offset >= layout.area.height {
+        return;
+    }
+    let row_area = Rect {
+        y: layout.area.y + row_offset,
+        height: 1,
+        ..layout.area
+    };
+
+    let color = theme.todo_state(&todo.state);
+    frame.render_widget(Clear, row_area);
+    render_text_input(
+        frame,
+        row_area,
+        &app.todo_text_input,
+        vec![
+            Span::raw("  ".repeat(todo.level)),
+            Span::styled(todo_state_symbol(&todo.state), Style::default().fg(color)),
+            Span::raw(" "),
+        ],
+        Block::default().style(Style::default().bg(theme.selection)),
+    );
+}
+
+/// A single-line popup for typing the due date of the open task
+fn render_due_date_edit(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
+    let area = frame.area();
+    let width = area.width.min(60);
+    let popup_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(3) / 2,
+        width,
+        height: area.height.min(3),
+    };
+
+    frame.render_widget(Clear, popup_area);
+    render_text_input(
+        frame,
+        popup_area,
+        &app.due_date_input,
+        Vec::new(),
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.focused_border))
+            .title("Due date (today, tomorrow, +3d, next monday, YYYY-MM-DD)"),
+    );
+}
+
+/// Short human description of how long ago something happened, e.g. `3h ago`
+fn relative_time(
+    time: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let elapsed = now.signed_duration_since(time);
+    if elapsed.num_minutes() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{}h ago", elapsed.num_hours())
+    } else if elapsed.num_days() < 30 {
+        format!("{}d ago", elapsed.num_days())
+    } else {
+        format!("on {}", time.format("%Y-%m-%d"))
+    }
+}
+
+/// One line per kind of relation, listing the related tasks by title
+fn relation_lines<'a>(
+    task_manager: &'a TaskManager,
+    theme: &Theme,
+    task: &'a norgdo::task::Task,
+) -> Vec<Line<'a>> {
+    let relations = task_manager.task_relations(task);
+
+    [
+        (RelationType::Requires, theme.error),
+        (RelationType::Blocks, theme.urgent),
+        (RelationType::Related, theme.muted),
+    ]
+    .into_iter()
+    .filter_map(|(relation_type, color)| {
+        let mut spans = vec![Span::styled(
+            format!("{}: ", relation_type.to_string()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )];
+        for (_, reference, target) in relations
+            .iter()
+            .filter(|(other_type, _, _)| *other_type == relation_type)
+        {
+            if spans.len() > 1 {
+                spans.push(Span::raw(", "));
+            }
+            spans.push(match target {
+                Some(target) => {
+                    Span::styled(target.title.as_str(), Style::default().fg(theme.text))
+                }
+                // The other task was renamed or deleted
+                None => Span::styled(
+                    format!("{} (not found)", reference),
+                    Style::default().fg(theme.muted),
+                ),
+            });
+        }
+        (spans.len() > 1).then(|| Line::from(spans))
+    })
+    .collect()
+}
+
+/// Draws a scrollbar over the right border of a bordered area, but only when the
+/// content doesn't fit
+fn render_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    state: &mut ScrollbarState,
+    content_length: usize,
+    viewport_length: usize,
+    position: usize,
+) {
+    if content_length <= viewport_length {
+        return;
     }
+
+    // The scrollbar reaches its end when position is the last index, not the last offset
+    let max_position = content_length - viewport_length;
+    *state = state
+        .content_length(max_position + 1)
+        .position(position.min(max_position));
+    let scrollbar = Scrollbar::default()
+        .orientation(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_symbol(Some("│"))
+        .thumb_symbol("█");
+    frame.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        state,
+    );
 }
 
 fn render_search(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -317,7 +1172,7 @@ fn render_search(app: &mut App, frame: &mut Frame) {
     let title = Paragraph::new("Search Tasks")
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
@@ -328,48 +1183,161 @@ fn render_search(app: &mut App, frame: &mut Frame) {
         );
     frame.render_widget(title, chunks[0]);
 
-    let input = Paragraph::new(app.search_query.as_str()).block(
+    render_text_input(
+        frame,
+        chunks[1],
+        &app.search_input,
+        Vec::new(),
         Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .title("Search Query"),
+            .title("Search Query (is:<state> tag:<tag> has:overdue text)"),
     );
-    frame.render_widget(input, chunks[1]);
 
     // Show search results
-    let search_results = app.task_manager.search_tasks(&app.search_query);
+    let search_results = app.task_manager.search_tasks(app.search_input.value());
     let result_items: Vec<ListItem> = search_results
         .iter()
-        .map(|task| {
+        .map(|(task, _)| {
             ListItem::new(Line::from(vec![
                 Span::styled(&task.title, Style::default().add_modifier(Modifier::BOLD)),
                 Span::styled(
-                    format!(" ({}% complete)", task.completion_percentage() as u8),
-                    Style::default().fg(Color::Gray),
+                    format!(
+                        " ({}% complete)",
+                        task.completion_percentage_with(app.task_manager.completion_rules()) as u8
+                    ),
+                    Style::default().fg(theme.muted),
                 ),
             ]))
         })
         .collect();
 
-    let results_list = List::new(result_items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .title(format!("Results ({})", search_results.len())),
+    let results_list = List::new(result_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(format!("Results ({})", search_results.len())),
+        )
+        .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
+        .highlight_style(Style::default().bg(theme.selection))
+        .highlight_symbol("» ");
+    frame.render_stateful_widget(results_list, chunks[2], &mut app.search_list_state);
+}
+
+fn render_task_picker(
+    app: &mut App,
+    frame: &mut Frame,
+    purpose: PickerPurpose,
+    query: &TextInput,
+    selected: usize,
+) {
+    let theme = &app.config.theme;
+    let popup_area = centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let title = match purpose {
+        PickerPurpose::Jump => "Jump to Task",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.focused_border))
+        .title(title)
+        .title_bottom(Line::from(" Enter: Open | Esc: Cancel ").centered());
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Query
+            Constraint::Min(0),    // Matching tasks
+        ])
+        .split(inner);
+
+    render_text_input(
+        frame,
+        chunks[0],
+        query,
+        vec![Span::styled("> ", Style::default().fg(theme.muted))],
+        Block::default(),
     );
-    frame.render_widget(results_list, chunks[2]);
+
+    let matches = app.task_manager.search_tasks(query.value());
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|(task, _)| {
+            let category = app.task_manager.category_of(task);
+            ListItem::new(Line::from(vec![
+                Span::styled(&task.title, Style::default().fg(theme.text)),
+                Span::styled(
+                    format!(" ({})", category.to_string()),
+                    Style::default().fg(theme.muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    // Fewer tasks may match after a reload
+    let selected = (!matches.is_empty()).then(|| selected.min(matches.len() - 1));
+    let mut list_state = ratatui::widgets::ListState::default().with_selected(selected);
+    let list = List::new(items)
+        .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
+        .highlight_style(Style::default().bg(theme.selection))
+        .highlight_symbol("» ");
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
 }
 
 fn render_task_wizard(app: &mut App, frame: &mut Frame, step: WizardStep) {
     match step {
         WizardStep::Title => render_wizard_title(app, frame),
         WizardStep::Description => render_wizard_description(app, frame),
+        WizardStep::DueDate => render_wizard_due_date(app, frame),
         WizardStep::Todos => render_wizard_todos(app, frame),
         WizardStep::Confirm => render_wizard_confirm(app, frame),
     }
 }
 
+/// The wizard's steps in a row with the current one highlighted, and the ones already
+/// done (which Left goes back to) set apart from the ones still ahead
+fn render_wizard_breadcrumb(frame: &mut Frame, theme: &Theme, step: WizardStep, area: Rect) {
+    let current = WizardStep::ALL
+        .iter()
+        .position(|other| *other == step)
+        .unwrap_or(0);
+
+    let mut spans = Vec::new();
+    for (index, other) in WizardStep::ALL.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled(" › ", Style::default().fg(theme.muted)));
+        }
+        let style = match index.cmp(&current) {
+            std::cmp::Ordering::Less => Style::default().fg(theme.text),
+            std::cmp::Ordering::Equal => Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+            std::cmp::Ordering::Greater => Style::default().fg(theme.muted),
+        };
+        spans.push(Span::styled(
+            format!("{}. {}", index + 1, other.name()),
+            style,
+        ));
+    }
+
+    let breadcrumb = Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Create New Task"),
+        );
+    frame.render_widget(breadcrumb, area);
+}
+
 fn render_wizard_title(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -379,78 +1347,150 @@ fn render_wizard_title(app: &mut App, frame: &mut Frame) {
         ])
         .split(frame.area());
 
-    let title = Paragraph::new("Create New Task - Step 1 of 4: Title")
-        .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )
-        .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        );
-    frame.render_widget(title, chunks[0]);
+    render_wizard_breadcrumb(frame, theme, WizardStep::Title, chunks[0]);
 
-    let input = Paragraph::new(app.wizard_data.title.as_str()).block(
+    render_text_input(
+        frame,
+        chunks[1],
+        &app.wizard_data.title,
+        Vec::new(),
         Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .title("Task Title (required)"),
     );
-    frame.render_widget(input, chunks[1]);
 
-    let help = Paragraph::new("Type the task title and press Enter to continue, Esc to cancel")
-        .style(Style::default().fg(Color::Gray))
+    let mut help_lines = Vec::new();
+    // Only a warning, the task can still be created under the same title
+    if app.wizard_title_exists() {
+        help_lines.push(Line::from(Span::styled(
+            "A task with this title exists",
+            Style::default().fg(theme.error),
+        )));
+    }
+    help_lines.push(Line::from(Span::styled(
+        "Type the task title and press Enter to continue, Esc to cancel",
+        Style::default().fg(theme.muted),
+    )));
+    let help = Paragraph::new(help_lines)
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
     frame.render_widget(help, chunks[2]);
 }
 
 fn render_wizard_description(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
+    let area = frame.area();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title("Description (optional)");
+    let width = usize::from(area.width.saturating_sub(2).max(1)); // Account for borders
+
+    // Wrap by character rather than by word so the cursor position can be computed exactly
+    let description = app.wizard_data.description.value();
+    let lines: Vec<Line> = wrap_chars(description, width)
+        .into_iter()
+        .map(Line::from)
+        .collect();
+    let before_cursor = wrap_chars(app.wizard_data.description.before_cursor(), width);
+    let cursor_row = before_cursor.len().saturating_sub(1) as u16;
+    let cursor_col = before_cursor.last().map_or(0, |line| line.chars().count()) as u16;
+
+    // Grow with the content, up to half of the screen
+    let input_height = (lines.len() as u16 + 2).clamp(5, (area.height / 2).max(5));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),            // Title
+            Constraint::Length(input_height), // Input
+            Constraint::Min(0),               // Help
+        ])
+        .split(area);
+
+    render_wizard_breadcrumb(frame, theme, WizardStep::Description, chunks[0]);
+
+    let inner = block.inner(chunks[1]);
+    let scroll = cursor_row.saturating_sub(inner.height.saturating_sub(1));
+    let input = Paragraph::new(lines).scroll((scroll, 0)).block(block);
+    frame.render_widget(input, chunks[1]);
+    frame.set_cursor_position((inner.x + cursor_col, inner.y + cursor_row - scroll));
+
+    let help = Paragraph::new(
+        "Type description and press Enter to continue, Alt+Enter for a new line, ← to go back, Esc to cancel",
+    )
+    .style(Style::default().fg(theme.muted))
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    frame.render_widget(help, chunks[2]);
+}
+
+fn render_wizard_due_date(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Title
-            Constraint::Length(5), // Input
+            Constraint::Length(3), // Input
             Constraint::Min(0),    // Help
         ])
         .split(frame.area());
 
-    let title = Paragraph::new("Create New Task - Step 2 of 4: Description")
-        .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )
-        .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        );
-    frame.render_widget(title, chunks[0]);
+    render_wizard_breadcrumb(frame, theme, WizardStep::DueDate, chunks[0]);
 
-    let input = Paragraph::new(app.wizard_data.description.as_str())
-        .wrap(Wrap { trim: true })
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .title("Description (optional)"),
-        );
-    frame.render_widget(input, chunks[1]);
+    render_text_input(
+        frame,
+        chunks[1],
+        &app.wizard_data.due_date,
+        Vec::new(),
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Due Date (optional)"),
+    );
 
-    let help =
-        Paragraph::new("Type description and press Enter to continue, ← to go back, Esc to cancel")
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true });
+    // Show what the input resolves to while typing, so a wrong guess is caught early
+    let mut help_lines = Vec::new();
+    match app.wizard_data.parsed_due_date() {
+        Ok(Some(due)) => help_lines.push(Line::from(Span::styled(
+            format!("Due {}", due.format("%A, %Y-%m-%d")),
+            Style::default().fg(theme.text),
+        ))),
+        Ok(None) => {}
+        Err(e) => help_lines.push(Line::from(Span::styled(
+            e.to_string(),
+            Style::default().fg(theme.error),
+        ))),
+    }
+    help_lines.push(Line::from(Span::styled(
+        "Type a date such as tomorrow, +3d, next monday or YYYY-MM-DD, or leave it empty. Enter to continue, ← to go back, Esc to cancel",
+        Style::default().fg(theme.muted),
+    )));
+    let help = Paragraph::new(help_lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
     frame.render_widget(help, chunks[2]);
 }
 
+/// Splits text into rows of at most `width` characters, starting a new row at each
+/// line break. Like the cursor after typing, the text continues on a new row once the
+/// last one is full, so that row may be empty.
+fn wrap_chars(text: &str, width: usize) -> Vec<String> {
+    let mut rows: Vec<String> = Vec::new();
+    let lines: Vec<&str> = text.split('\n').collect();
+    for (index, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        rows.extend(chars.chunks(width).map(|chunk| chunk.iter().collect()));
+        let is_last = index == lines.len() - 1;
+        if chars.is_empty() || (is_last && chars.len().is_multiple_of(width)) {
+            rows.push(String::new());
+        }
+    }
+    rows
+}
+
 fn render_wizard_todos(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -461,33 +1501,32 @@ fn render_wizard_todos(app: &mut App, frame: &mut Frame) {
         ])
         .split(frame.area());
 
-    let title = Paragraph::new("Create New Task - Step 3 of 4: TODO Items")
-        .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )
-        .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        );
-    frame.render_widget(title, chunks[0]);
+    render_wizard_breadcrumb(frame, theme, WizardStep::Todos, chunks[0]);
 
-    let input_title = if app.wizard_data.editing_todo_index.is_some() {
-        "Edit TODO Item"
-    } else {
-        "Add TODO Item"
-    };
+    let input_title = format!(
+        "{} (indent: {})",
+        if app.wizard_data.editing_todo_index.is_some() {
+            "Edit TODO Item"
+        } else {
+            "Add TODO Item"
+        },
+        app.wizard_data.current_todo_indent
+    );
 
-    let input = Paragraph::new(app.wizard_data.current_todo.as_str()).block(
+    let current_state = &app.wizard_data.current_todo_state;
+    render_text_input(
+        frame,
+        chunks[1],
+        &app.wizard_data.current_todo,
+        vec![Span::styled(
+            format!("({}) ", current_state.to_norg_char()),
+            Style::default().fg(theme.todo_state(current_state)),
+        )],
         Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .title(input_title),
     );
-    frame.render_widget(input, chunks[1]);
 
     // Show existing todos with selection highlighting
     let todo_items: Vec<ListItem> = app
@@ -497,23 +1536,38 @@ fn render_wizard_todos(app: &mut App, frame: &mut Frame) {
         .enumerate()
         .map(|(i, todo)| {
             let is_selected = app.wizard_data.selected_todo_index == Some(i);
+            let is_editing = app.wizard_data.editing_todo_index == Some(i);
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.focused_border)
                     .add_modifier(Modifier::BOLD)
+            } else if is_editing {
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::ITALIC)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(theme.text)
             };
 
-            let prefix = if is_selected { "» " } else { "  " };
+            let prefix = if is_selected {
+                "» "
+            } else if is_editing {
+                "✎ "
+            } else {
+                "  "
+            };
 
             ListItem::new(Line::from(vec![
                 Span::styled(
                     format!("{}{}. ", prefix, i + 1),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::raw("  ".repeat(todo.level.saturating_sub(1))),
+                Span::styled(
+                    format!("({}) ", todo.state.to_norg_char()),
+                    Style::default().fg(theme.todo_state(&todo.state)),
                 ),
-                Span::raw("[ ] "),
-                Span::styled(todo, style),
+                Span::styled(&todo.text, style),
             ]))
         })
         .collect();
@@ -526,14 +1580,15 @@ fn render_wizard_todos(app: &mut App, frame: &mut Frame) {
     );
     frame.render_widget(todos_list, chunks[2]);
 
-    let help = Paragraph::new("Enter: Add item | Empty+Enter/Tab: Continue | ↑↓: Select | Del: Delete | F2: Edit | ←: Back | Esc: Cancel")
-        .style(Style::default().fg(Color::Gray))
+    let help = Paragraph::new("Enter: Add item | Empty+Enter: Continue | Tab/Shift+Tab: Indent/Outdent | Ctrl+S: Cycle state | ↑↓: Select | Del: Delete | F2: Edit | ←: Back | Esc: Cancel")
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
     frame.render_widget(help, chunks[3]);
 }
 
 fn render_wizard_confirm(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -543,19 +1598,7 @@ fn render_wizard_confirm(app: &mut App, frame: &mut Frame) {
         ])
         .split(frame.area());
 
-    let title = Paragraph::new("Create New Task - Step 4 of 4: Confirm")
-        .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )
-        .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        );
-    frame.render_widget(title, chunks[0]);
+    render_wizard_breadcrumb(frame, theme, WizardStep::Confirm, chunks[0]);
 
     // Create summary content
     let mut summary_lines = vec![format!("Title: {}", app.wizard_data.title), String::new()];
@@ -565,10 +1608,22 @@ fn render_wizard_confirm(app: &mut App, frame: &mut Frame) {
         summary_lines.push(String::new());
     }
 
+    if let Ok(Some(due)) = app.wizard_data.parsed_due_date() {
+        summary_lines.push(format!("Due: {}", due.format("%A, %Y-%m-%d")));
+        summary_lines.push(String::new());
+    }
+
     if !app.wizard_data.todos.is_empty() {
         summary_lines.push(format!("TODO Items ({}):", app.wizard_data.todos.len()));
         for (i, todo) in app.wizard_data.todos.iter().enumerate() {
-            summary_lines.push(format!("  {}. ( ) {}", i + 1, todo));
+            let indent = "  ".repeat(todo.level.saturating_sub(1));
+            summary_lines.push(format!(
+                "  {}. {}({}) {}",
+                i + 1,
+                indent,
+                todo.state.to_norg_char(),
+                todo.text
+            ));
         }
     } else {
         summary_lines.push("No TODO items".to_string());
@@ -576,8 +1631,9 @@ fn render_wizard_confirm(app: &mut App, frame: &mut Frame) {
 
     let summary_text = summary_lines.join("\n");
 
+    // Don't trim, the leading whitespace shows the TODO hierarchy
     let summary = Paragraph::new(summary_text)
-        .wrap(Wrap { trim: true })
+        .wrap(Wrap { trim: false })
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -587,30 +1643,241 @@ fn render_wizard_confirm(app: &mut App, frame: &mut Frame) {
     frame.render_widget(summary, chunks[1]);
 
     let help = Paragraph::new("Enter/Y: Create task | ←: Go back to TODOs | Esc/N: Cancel")
-        .style(Style::default().fg(Color::Gray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
     frame.render_widget(help, chunks[2]);
 }
 
-fn render_error_popup(frame: &mut Frame, error: &str) {
-    let popup_area = centered_rect(60, 20, frame.area());
+fn render_quick_add(app: &mut App, frame: &mut Frame, title: &str) {
+    let theme = &app.config.theme;
+    let area = centered_rect(60, 100, frame.area());
+    let popup_area = Rect {
+        y: area.y + area.height.saturating_sub(3) / 2,
+        height: area.height.min(3),
+        ..area
+    };
+
+    frame.render_widget(Clear, popup_area);
+    render_text_input(
+        frame,
+        popup_area,
+        &app.new_task_title,
+        Vec::new(),
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.title))
+            .title(format!("{} | Enter: Create | Esc: Cancel", title)),
+    );
+}
+
+fn render_template_picker(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
+    let popup_area = centered_rect(50, 50, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = app
+        .templates
+        .iter()
+        .map(|name| ListItem::new(Span::styled(name.as_str(), Style::default().fg(theme.text))))
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.title))
+                .title("Choose a Template | Enter: Select | Esc: Cancel"),
+        )
+        .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
+        .highlight_style(Style::default().bg(theme.selection))
+        .highlight_symbol("» ");
+    frame.render_stateful_widget(list, popup_area, &mut app.template_list_state);
+}
+
+fn render_parse_errors(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
+    let popup_area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = app
+        .task_manager
+        .parse_errors()
+        .iter()
+        .map(|(path, error)| {
+            let file_name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().to_string(),
+            );
+            let lines = std::iter::once(Line::from(Span::styled(
+                file_name,
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .chain(error.lines().map(|line| {
+                Line::from(Span::styled(
+                    format!("  {}", line),
+                    Style::default().fg(theme.muted),
+                ))
+            }))
+            .collect::<Vec<_>>();
+            ListItem::new(lines)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.error))
+                .title("Files that failed to parse | Esc: Close"),
+        )
+        .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
+        .highlight_style(Style::default().bg(theme.selection))
+        .highlight_symbol("» ");
+    frame.render_stateful_widget(list, popup_area, &mut app.parse_error_list_state);
+}
+
+fn render_confirm_quit(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
+    let popup_area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let unsaved = app.task_manager.unsaved_count();
+    let text = vec![
+        Line::from(format!(
+            "{} task{} ha{} unsaved changes.",
+            unsaved,
+            if unsaved == 1 { "" } else { "s" },
+            if unsaved == 1 { "s" } else { "ve" }
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "y: Quit without saving | s: Save and quit | n/Esc: Cancel",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let popup = Paragraph::new(text)
+        .style(Style::default().fg(theme.text))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.focused_border))
+                .title("Quit norgdo?"),
+        );
+    frame.render_widget(popup, popup_area);
+}
 
+fn render_confirm_discard_wizard(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
+    let popup_area = centered_rect(50, 20, frame.area());
     frame.render_widget(Clear, popup_area);
 
-    let error_widget = Paragraph::new(error)
-        .style(Style::default().fg(Color::Red))
+    let text = vec![
+        Line::from("What you entered for this task will be lost."),
+        Line::from(""),
+        Line::from(Span::styled(
+            "y: Discard | n/Esc: Keep editing",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let popup = Paragraph::new(text)
+        .style(Style::default().fg(theme.text))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Red))
-                .title("Error"),
+                .border_style(Style::default().fg(theme.focused_border))
+                .title("Discard this task? y/N"),
+        );
+    frame.render_widget(popup, popup_area);
+}
+
+/// A spinner while tasks load in the background. Quick loads are over before it would
+/// only flash, so it waits a moment before showing up.
+fn render_loading(frame: &mut Frame, theme: &Theme, elapsed: Duration) {
+    const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    if elapsed < Duration::from_millis(150) {
+        return;
+    }
+
+    let spinner = FRAMES[(elapsed.as_millis() / 100) as usize % FRAMES.len()];
+    let text = format!("{} Loading tasks...", spinner);
+    let area = frame.area();
+    let width = (text.chars().count() as u16 + 4).min(area.width);
+    let popup_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(3) / 2,
+        width,
+        height: 3.min(area.height),
+    };
+
+    frame.render_widget(Clear, popup_area);
+    let loading = Paragraph::new(text)
+        .style(Style::default().fg(theme.text))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.focused_border)),
         );
+    frame.render_widget(loading, popup_area);
+}
 
-    frame.render_widget(error_widget, popup_area);
+/// Stacks notifications in the bottom right corner, newest at the bottom. The oldest
+/// are left out when they don't all fit. The newest error is the one Enter/Esc dismisses.
+fn render_notifications(frame: &mut Frame, notifications: &[Notification], theme: &Theme) {
+    let area = frame.area();
+    let width = area.width.min(60);
+    let text_width = usize::from(width.saturating_sub(2)).max(1);
+    let dismissable = notifications
+        .iter()
+        .rposition(|n| n.severity == Severity::Error);
+    // Keep clear of the status bar
+    let mut bottom = area.height.saturating_sub(1);
+
+    for (index, notification) in notifications.iter().enumerate().rev() {
+        let rows = wrap_chars(&notification.message, text_width);
+        let height = (rows.len() as u16 + 2).min(bottom);
+        if height < 3 {
+            break;
+        }
+        bottom -= height;
+        let notification_area = Rect::new(area.width - width, bottom, width, height);
+
+        let (title, color) = match notification.severity {
+            Severity::Success => ("Done", theme.done),
+            Severity::Info => ("Info", theme.focused_border),
+            Severity::Error => ("Error", theme.error),
+        };
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color))
+            .title(title);
+        if dismissable == Some(index) {
+            block = block.title_bottom(Line::from(" Enter/Esc: dismiss ").centered());
+        }
+        let lines: Vec<Line> = rows.into_iter().map(Line::from).collect();
+
+        frame.render_widget(Clear, notification_area);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(Style::default().fg(color))
+                .block(block),
+            notification_area,
+        );
+    }
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -634,93 +1901,39 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 }
 
 fn render_help(app: &mut App, frame: &mut Frame) {
+    let theme = &app.config.theme;
     let popup_area = centered_rect(80, 70, frame.area());
 
     frame.render_widget(Clear, popup_area);
 
-    let help_content = vec![
-        "DASHBOARD NAVIGATION:",
-        "  Left/Right (← →)    Switch between kanban columns",
-        "  Up/Down (↑ ↓)       Navigate within a column",
-        "  Enter               Open selected task details",
-        "",
-        "TASK MANAGEMENT:",
-        "  n                   Create new task",
-        "  r                   Refresh tasks from disk",
-        "  /                   Search tasks",
-        "",
-        "TASK CREATION WIZARD:",
-        "  Enter               Continue to next step / Add TODO item",
-        "  Tab                 Skip to confirmation (from TODO step)",
-        "  Left (←)            Go back to previous step",
-        "  Up/Down (↑ ↓)       Navigate TODO list (step 3)",
-        "  Backspace           Delete character",
-        "  Delete              Remove selected TODO item (step 3)",
-        "  F2                  Edit selected TODO item (step 3)",
-        "  Y/N                 Confirm/Cancel task creation (final step)",
-        "  Esc                 Cancel wizard and return to dashboard",
-        "",
-        "TASK DETAIL VIEW:",
-        "  Up/Down (↑ ↓)       Navigate between TODO items",
-        "  Space               Open TODO state selection dialog",
-        "  s                   Save changes to file",
-        "  Esc/q               Return to dashboard",
-        "",
-        "TODO STATE SELECTION:",
-        "  Up/Down (↑ ↓)       Navigate state options",
-        "  Enter/Space         Select state and apply",
-        "  Esc/q               Cancel state selection",
-        "",
-        "TODO STATES:",
-        "   Undone            Task not started",
-        "   Pending           Task in progress",
-        "   Done              Task completed",
-        "   Urgent            High priority task",
-        "   Uncertain         Task status unclear",
-        "   On Hold           Task paused",
-        "   Cancelled         Task cancelled",
-        "   Recurring         Recurring task",
-        "",
-        "GENERAL:",
-        "  ?                   Show/hide this help",
-        "  q                   Quit application",
-        "",
-        "HELP NAVIGATION:",
-        "  Up/Down (↑ ↓)       Scroll help content",
-        "  Page Up/Down        Scroll faster",
-        "  Home                Go to top",
-        "  End                 Go to bottom",
-        "",
-        "Press ? or Esc/q to close this help",
-    ];
+    let (help_content, section_starts) = help::help_content(app.config.auto_save);
 
-    // Calculate visible content based on scroll offset and available height
+    // The handler keeps the offset within the bounds recorded here, which are only
+    // clamped again in case the terminal grew since
     let content_height = popup_area.height.saturating_sub(2) as usize; // Account for borders
-    let scroll_offset = app.help_scroll_offset as usize;
+    app.help_page_height = content_height as u16;
     let max_scroll = help_content.len().saturating_sub(content_height);
+    app.help_max_scroll = max_scroll;
+    let scroll = app.help_list_state.offset().min(max_scroll);
+    *app.help_list_state.offset_mut() = scroll;
 
-    // Clamp scroll offset to valid range and update app state
-    let actual_scroll = scroll_offset.min(max_scroll);
-    app.help_scroll_offset = actual_scroll as u16;
-
-    // Get visible lines
-    let visible_content: Vec<&str> = help_content
+    let items: Vec<ListItem> = help_content
         .iter()
-        .skip(actual_scroll)
-        .take(content_height)
-        .cloned()
+        .enumerate()
+        .map(|(index, line)| {
+            if section_starts.contains(&index) {
+                ListItem::new(Line::from(Span::styled(
+                    line.as_str(),
+                    Style::default()
+                        .fg(theme.title)
+                        .add_modifier(Modifier::BOLD),
+                )))
+            } else {
+                ListItem::new(Line::from(line.as_str()))
+            }
+        })
         .collect();
 
-    let help_text = visible_content.join("\n");
-
-    // Create the main content area and scrollbar area
-    let content_area = Rect {
-        x: popup_area.x,
-        y: popup_area.y,
-        width: popup_area.width.saturating_sub(1), // Leave space for scrollbar
-        height: popup_area.height,
-    };
-
     let scrollbar_area = Rect {
         x: popup_area.x + popup_area.width.saturating_sub(1),
         y: popup_area.y + 1, // Start below the top border
@@ -728,29 +1941,27 @@ fn render_help(app: &mut App, frame: &mut Frame) {
         height: popup_area.height.saturating_sub(2), // Exclude top and bottom borders
     };
 
-    // Render the help content
-    let help_widget = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Left)
-        .wrap(Wrap { trim: true })
+    let help_list = List::new(items)
+        .style(Style::default().fg(theme.text))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title("Help - Norgdo Terminal Task Manager"),
+                .border_style(Style::default().fg(theme.title))
+                .title(format!(
+                    "Help - Norgdo Terminal Task Manager | {}",
+                    help::HELP_SECTIONS[app.help_section].title
+                )),
         );
+    frame.render_stateful_widget(help_list, popup_area, &mut app.help_list_state);
 
-    frame.render_widget(help_widget, content_area);
-
-    // Update and render the scrollbar
-    // FIXME: The damn scrollbar is not reaching the bottom when the help content reaches EOF
-    // ratatui documentation is terrible so it might take a while for me to fix it
+    // The scrollbar tracks the offset, so it has one position per reachable offset
+    // and reaches the bottom together with the content
     app.help_scrollbar_state = app
         .help_scrollbar_state
-        .content_length(help_content.len())
-        .viewport_content_length(visible_content.len())
-        .position(actual_scroll);
+        .content_length(max_scroll + 1)
+        .viewport_content_length(content_height)
+        .position(scroll);
 
     let scrollbar = Scrollbar::default()
         .orientation(ScrollbarOrientation::VerticalRight)
@@ -763,7 +1974,8 @@ fn render_help(app: &mut App, frame: &mut Frame) {
 }
 
 fn render_todo_state_select(app: &mut App, frame: &mut Frame, task_id: &str, todo_index: usize) {
-    use crate::task::TodoState;
+    let theme = &app.config.theme;
+    use norgdo::task::TodoState;
 
     // Get the current TODO being edited
     let current_todo = app
@@ -791,7 +2003,7 @@ fn render_todo_state_select(app: &mut App, frame: &mut Frame, task_id: &str, tod
     let title = Paragraph::new("Select TODO State")
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
@@ -799,13 +2011,13 @@ fn render_todo_state_select(app: &mut App, frame: &mut Frame, task_id: &str, tod
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(theme.title)),
         );
     frame.render_widget(title, chunks[0]);
 
     // Current TODO info
     let current_info = if let Some(todo) = current_todo {
-        format!("{} ({})", todo.text, todo.state.to_string())
+        format!("{} ({})", todo.text, todo.state_label())
     } else {
         "TODO not found".to_string()
     };
@@ -821,41 +2033,11 @@ fn render_todo_state_select(app: &mut App, frame: &mut Frame, task_id: &str, tod
     frame.render_widget(info_widget, chunks[1]);
 
     // States list
-    let states = vec![
-        TodoState::Undone,
-        TodoState::Pending,
-        TodoState::Done,
-        TodoState::Urgent,
-        TodoState::Uncertain,
-        TodoState::OnHold,
-        TodoState::Cancelled,
-        TodoState::Recurring,
-    ];
-
-    let state_items: Vec<ListItem> = states
+    let state_items: Vec<ListItem> = TodoState::ALL
         .iter()
         .map(|state| {
-            let symbol = match state {
-                TodoState::Done => "",
-                TodoState::Cancelled => "",
-                TodoState::Pending => "",
-                TodoState::Urgent => "",
-                TodoState::OnHold => "",
-                TodoState::Uncertain => "",
-                TodoState::Recurring => "",
-                TodoState::Undone => "",
-            };
-
-            let color = match state {
-                TodoState::Done => Color::Green,
-                TodoState::Cancelled => Color::Red,
-                TodoState::Urgent => Color::Yellow,
-                TodoState::Pending => Color::Blue,
-                TodoState::Uncertain => Color::Magenta,
-                TodoState::OnHold => Color::Cyan,
-                TodoState::Recurring => Color::LightYellow,
-                TodoState::Undone => Color::White,
-            };
+            let symbol = todo_state_symbol(state);
+            let color = theme.todo_state(state);
 
             ListItem::new(Line::from(vec![
                 Span::styled(
@@ -866,7 +2048,7 @@ fn render_todo_state_select(app: &mut App, frame: &mut Frame, task_id: &str, tod
                 Span::styled(state.to_string(), Style::default().fg(color)),
                 Span::styled(
                     format!(" ({})", state.to_norg_char()),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme.muted),
                 ),
             ]))
         })
@@ -882,7 +2064,7 @@ fn render_todo_state_select(app: &mut App, frame: &mut Frame, task_id: &str, tod
         .highlight_spacing(ratatui::widgets::HighlightSpacing::Never)
         .highlight_style(
             Style::default()
-                .bg(Color::Black)
+                .bg(theme.selection)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("» ");
@@ -890,8 +2072,8 @@ fn render_todo_state_select(app: &mut App, frame: &mut Frame, task_id: &str, tod
     frame.render_stateful_widget(states_list, chunks[2], &mut app.todo_state_list_state);
 
     // Help
-    let help = Paragraph::new("↑↓: Navigate | Enter/Space: Select | Esc/q: Cancel")
-        .style(Style::default().fg(Color::Gray))
+    let help = Paragraph::new("↑↓: Navigate | Enter/Space: Select | Esc: Cancel")
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
         .block(
@@ -0,0 +1,70 @@
+use color_eyre::Result;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+/// Watches the data directory, including project subdirectories, for changes made
+/// outside of norgdo.
+///
+/// Notifications are delivered from notify's background thread over a channel,
+/// so the main loop can check for them without blocking. norgdo's own writes are
+/// reported too, so those are ignored for a short while after they happen.
+pub struct TaskWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+    own_writes: Vec<(PathBuf, Instant)>, // Files norgdo wrote and when
+    ignore_for: Duration,                // How long events for those are ignored
+}
+
+impl TaskWatcher {
+    /// Watches `data_dir`, ignoring events for norgdo's own writes for `ignore_for`
+    pub fn new(data_dir: &Path, ignore_for: Duration) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(data_dir, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+            own_writes: Vec::new(),
+            ignore_for,
+        })
+    }
+
+    /// Records files norgdo just wrote, so the events they cause aren't taken for
+    /// outside changes
+    pub fn ignore_own_writes(&mut self, paths: Vec<PathBuf>) {
+        let now = Instant::now();
+        self.own_writes
+            .extend(paths.into_iter().map(|path| (path, now)));
+    }
+
+    /// Drains pending notifications, returning the `.norg` files that changed.
+    /// Editors usually emit several events per save, so each file is listed once.
+    pub fn changed_files(&mut self) -> Vec<PathBuf> {
+        let ignore_for = self.ignore_for;
+        self.own_writes
+            .retain(|(_, written)| written.elapsed() < ignore_for);
+
+        let mut changed = Vec::new();
+        while let Ok(event) = self.receiver.try_recv() {
+            let Ok(event) = event else {
+                continue;
+            };
+            if event.kind.is_access() {
+                continue;
+            }
+            for path in event.paths {
+                let is_own_write = self.own_writes.iter().any(|(written, _)| *written == path);
+                if path.extension().is_some_and(|ext| ext == "norg")
+                    && !is_own_write
+                    && !changed.contains(&path)
+                {
+                    changed.push(path);
+                }
+            }
+        }
+        changed
+    }
+}